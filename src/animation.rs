@@ -1,5 +1,6 @@
 pub mod backup;
 pub mod offset;
+pub mod pulse;
 pub mod transition;
 
 use bevy::prelude::*;
@@ -15,7 +16,12 @@ pub(super) fn plugin(app: &mut App) {
         Pause.on_edge(unpause_tweens::<Sprite>, pause_tweens::<Sprite>),
     );
 
-    app.add_plugins((backup::plugin, offset::plugin, transition::plugin));
+    app.add_plugins((
+        backup::plugin,
+        offset::plugin,
+        pulse::plugin,
+        transition::plugin,
+    ));
 }
 
 fn unpause_tweens<C: Component>(mut tween_query: Query<&mut Animator<C>>) {
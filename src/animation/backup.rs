@@ -3,11 +3,12 @@ use bevy::reflect::GetTypeRegistration;
 use bevy::transform::systems::propagate_transforms;
 use bevy::transform::systems::sync_simple_transforms;
 
+use crate::core::PostColorSet;
 use crate::core::PostTransformSet;
 use crate::util::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
-    app.configure::<Backup<Transform>>();
+    app.configure::<(Backup<Transform>, Backup<Sprite>)>();
 
     // Fix `GlobalTransform` after restoring `Transform`.
     app.add_systems(
@@ -30,7 +31,12 @@ impl<C: Component + Clone + Reflect + FromReflect + TypePath + GetTypeRegistrati
         // This has to run before `UiSystem::Focus` in `PreUpdate` anyways, so may as well
         // go all the way back to `First`.
         app.add_systems(First, restore_backup::<C>);
-        app.add_systems(PostUpdate, save_backup::<C>.in_set(PostTransformSet::Save));
+        app.add_systems(
+            PostUpdate,
+            save_backup::<C>
+                .in_set(PostTransformSet::Save)
+                .in_set(PostColorSet::Save),
+        );
     }
 }
 
@@ -0,0 +1,183 @@
+use std::marker::PhantomData;
+
+use bevy::prelude::*;
+
+use crate::core::theme::ColorMut;
+use crate::core::theme::ThemeColor;
+use crate::core::theme::ThemeConfig;
+use crate::core::PostColorSet;
+use crate::core::PostTransformSet;
+use crate::game::audio::music::Beat;
+use crate::util::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.configure::<(
+        PulseScale,
+        PulseOffset,
+        PulseColorFor<Sprite>,
+        PulseColorFor<UiImage>,
+        PulseColorFor<BackgroundColor>,
+        PulseColorFor<BorderColor>,
+        PulseColorFor<Outline>,
+    )>();
+}
+
+/// The timing of a pulse that repeats with the music.
+#[derive(Reflect, Copy, Clone)]
+pub struct OnBeatPulse {
+    /// The number of eighth-beats between pulses.
+    pub division: usize,
+    pub ease: PulseEase,
+}
+
+impl Default for OnBeatPulse {
+    fn default() -> Self {
+        Self {
+            division: 8,
+            ease: PulseEase::Quadratic,
+        }
+    }
+}
+
+impl OnBeatPulse {
+    pub fn new(division: usize) -> Self {
+        Self {
+            division,
+            ..default()
+        }
+    }
+
+    pub fn with_ease(mut self, ease: PulseEase) -> Self {
+        self.ease = ease;
+        self
+    }
+
+    /// The strength of the pulse (from 1 at the start of a pulse, decaying to 0).
+    fn strength(self, beat: &Beat) -> f32 {
+        let division = self.division.max(1) as f64;
+        let t = (beat.position() % division / division) as f32;
+        self.ease.apply(1.0 - t)
+    }
+
+    /// The number of pulses so far.
+    fn count(self, beat: &Beat) -> usize {
+        beat.total / self.division.max(1)
+    }
+}
+
+/// How a pulse decays over time.
+#[derive(Reflect, Copy, Clone, Default)]
+pub enum PulseEase {
+    Linear,
+    #[default]
+    Quadratic,
+    Cubic,
+}
+
+impl PulseEase {
+    fn apply(self, t: f32) -> f32 {
+        match self {
+            Self::Linear => t,
+            Self::Quadratic => t * t,
+            Self::Cubic => t * t * t,
+        }
+    }
+}
+
+/// Bounce an entity's scale with the music.
+/// Requires `Backup<Transform>`.
+#[derive(Component, Reflect, Copy, Clone)]
+#[reflect(Component)]
+pub struct PulseScale(pub OnBeatPulse, pub f32);
+
+impl Configure for PulseScale {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(
+            PostUpdate,
+            apply_pulse_scale.in_set(PostTransformSet::Blend),
+        );
+    }
+}
+
+fn apply_pulse_scale(beat: Res<Beat>, mut pulse_query: Query<(&PulseScale, &mut Transform)>) {
+    for (pulse, mut transform) in &mut pulse_query {
+        transform.scale *= 1.0 + pulse.1 * pulse.0.strength(&beat);
+    }
+}
+
+/// Wiggle an entity's position with the music, alternating direction each pulse.
+/// Requires `Backup<Transform>`.
+#[derive(Component, Reflect, Copy, Clone)]
+#[reflect(Component)]
+pub struct PulseOffset(pub OnBeatPulse, pub Vec2);
+
+impl Configure for PulseOffset {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(
+            PostUpdate,
+            apply_pulse_offset.in_set(PostTransformSet::Blend),
+        );
+    }
+}
+
+fn apply_pulse_offset(beat: Res<Beat>, mut pulse_query: Query<(&PulseOffset, &mut Transform)>) {
+    for (pulse, mut transform) in &mut pulse_query {
+        let sign = if pulse.0.count(&beat) % 2 == 0 {
+            1.0
+        } else {
+            -1.0
+        };
+        transform.translation += (sign * pulse.1 * pulse.0.strength(&beat)).extend(0.0);
+    }
+}
+
+/// Flash an entity's color towards a theme color with the music.
+/// Requires the color to be reset every frame (e.g. by `ThemeColorFor<C>` or `Backup<C>`).
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct PulseColorFor<C: Component + ColorMut> {
+    pub pulse: OnBeatPulse,
+    pub color: ThemeColor,
+    /// The maximum fraction of the way to blend towards the flash color.
+    pub amount: f32,
+    #[reflect(ignore)]
+    _phantom: PhantomData<C>,
+}
+
+impl<C: Component + ColorMut + TypePath> Configure for PulseColorFor<C> {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(
+            PostUpdate,
+            apply_pulse_color::<C>.in_set(PostColorSet::Blend),
+        );
+    }
+}
+
+impl<C: Component + ColorMut> PulseColorFor<C> {
+    pub fn new(pulse: OnBeatPulse, color: ThemeColor, amount: f32) -> Self {
+        Self {
+            pulse,
+            color,
+            amount,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+fn apply_pulse_color<C: Component + ColorMut>(
+    beat: Res<Beat>,
+    theme: ConfigRef<ThemeConfig>,
+    mut pulse_query: Query<(&PulseColorFor<C>, &mut C)>,
+) {
+    let palette = &r!(theme.get().and_then(ThemeConfig::active)).colors;
+    for (pulse, mut color) in &mut pulse_query {
+        let color = color.color_mut();
+        *color = color.better_mix(
+            &palette[pulse.color],
+            pulse.amount * pulse.pulse.strength(&beat),
+        );
+    }
+}
@@ -5,8 +5,10 @@ pub mod audio;
 pub mod camera;
 #[cfg(feature = "dev")]
 pub mod debug;
+pub mod locale;
 pub mod pause;
 pub mod physics;
+pub mod settings;
 pub mod state;
 pub mod theme;
 pub mod window;
@@ -39,8 +41,10 @@ pub(super) fn plugin(app: &mut App) {
         camera::plugin,
         #[cfg(feature = "dev")]
         debug::plugin,
+        locale::plugin,
         pause::plugin,
         physics::plugin,
+        settings::plugin,
         theme::plugin,
     ));
 }
@@ -1,3 +1,5 @@
+pub mod effect;
+
 use bevy::core_pipeline::tonemapping::Tonemapping;
 use bevy::prelude::*;
 use bevy::render::camera::ScalingMode;
@@ -5,6 +7,7 @@ use pyri_state::prelude::*;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::animation::backup::Backup;
 use crate::core::pause::Pause;
 use crate::core::UpdateSet;
 use crate::screen::Screen;
@@ -19,12 +22,40 @@ pub(super) fn plugin(app: &mut App) {
         SmoothFollow,
         AbsoluteScale,
     )>();
+
+    app.add_plugins(effect::plugin);
 }
 
 #[derive(Asset, Reflect, Serialize, Deserialize)]
-struct CameraConfig {
+pub struct CameraConfig {
     scaling_mode: ScalingMode,
     follow_rate: Vec2,
+
+    // Screen shake:
+    /// Trauma lost per second.
+    pub shake_decay: f32,
+    /// The maximum shake offset (in pixels), reached at full trauma.
+    pub shake_offset: Vec2,
+    /// Trauma added when the player is hurt.
+    pub shake_hurt: f32,
+    /// Trauma added when a big enemy dies.
+    pub shake_kill: f32,
+    /// The minimum max health for an enemy to count as big.
+    pub shake_kill_health: f32,
+
+    // Zoom punch:
+    /// The exponential decay rate of zoom punches.
+    pub punch_decay: f32,
+    pub punch_level_up: f32,
+    pub punch_beat: f32,
+    /// The number of beats between zoom punches on the beat (or 0 to disable).
+    pub punch_beat_interval: usize,
+
+    // Hitstop:
+    /// The duration (in seconds) to freeze physics for on a heavy hit.
+    pub hitstop_secs: f32,
+    /// The minimum damage for a hit to count as heavy.
+    pub hitstop_damage: f32,
 }
 
 impl Config for CameraConfig {
@@ -74,6 +105,7 @@ impl FromWorld for CameraRoot {
                         rate: Vec2::splat(100.0),
                     },
                     IsDefaultUiCamera,
+                    Backup::<Transform>::default(),
                 ))
                 .id(),
         }
@@ -0,0 +1,133 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use pyri_state::prelude::*;
+use rand::Rng as _;
+
+use crate::core::camera::CameraConfig;
+use crate::core::camera::CameraRoot;
+use crate::core::pause::Pause;
+use crate::core::PostTransformSet;
+use crate::core::UpdateSet;
+use crate::screen::Screen;
+use crate::util::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.configure::<CameraEffects>();
+}
+
+/// Transient camera effects: screen shake, zoom punch and hitstop.
+#[derive(Resource, Reflect, Default)]
+#[reflect(Resource)]
+pub struct CameraEffects {
+    /// Screen shake strength (from 0 to 1), which decays over time.
+    pub trauma: f32,
+    /// Zoom punch strength, which decays over time.
+    pub punch: f32,
+    /// The remaining real time (in seconds) to freeze physics for.
+    pub hitstop: f32,
+}
+
+impl Configure for CameraEffects {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.init_resource::<Self>();
+        app.add_systems(StateFlush, Screen::ANY.on_exit(reset_camera_effects));
+        app.add_systems(
+            Update,
+            (
+                decay_camera_effects.run_if(Pause::is_disabled),
+                apply_hitstop.run_if(Pause::is_disabled),
+            )
+                .in_set(UpdateSet::TickTimers),
+        );
+        app.add_systems(
+            PostUpdate,
+            (apply_screen_shake, apply_zoom_punch)
+                .run_if(Pause::is_disabled)
+                .in_set(PostTransformSet::Blend),
+        );
+    }
+}
+
+impl CameraEffects {
+    pub fn add_trauma(&mut self, trauma: f32) {
+        self.trauma = (self.trauma + trauma).min(1.0);
+    }
+
+    pub fn punch(&mut self, punch: f32) {
+        self.punch = self.punch.max(punch);
+    }
+
+    pub fn hitstop(&mut self, secs: f32) {
+        self.hitstop = self.hitstop.max(secs);
+    }
+}
+
+fn reset_camera_effects(
+    mut effects: ResMut<CameraEffects>,
+    mut physics_time: ResMut<Time<Physics>>,
+) {
+    if effects.hitstop > 0.0 {
+        physics_time.unpause();
+    }
+    *effects = default();
+}
+
+fn decay_camera_effects(
+    config: ConfigRef<CameraConfig>,
+    time: Res<Time>,
+    mut effects: ResMut<CameraEffects>,
+) {
+    let config = r!(config.get());
+    let dt = time.delta_seconds();
+    effects.trauma = (effects.trauma - config.shake_decay * dt).max(0.0);
+    effects.punch *= (-config.punch_decay * dt).exp();
+}
+
+fn apply_hitstop(
+    time: Res<Time<Real>>,
+    mut effects: ResMut<CameraEffects>,
+    mut physics_time: ResMut<Time<Physics>>,
+) {
+    if effects.hitstop <= 0.0 {
+        return;
+    }
+
+    effects.hitstop -= time.delta_seconds();
+    if effects.hitstop > 0.0 {
+        physics_time.pause();
+    } else {
+        physics_time.unpause();
+    }
+}
+
+/// Requires `Backup<Transform>` on the camera.
+fn apply_screen_shake(
+    config: ConfigRef<CameraConfig>,
+    camera_root: Res<CameraRoot>,
+    effects: Res<CameraEffects>,
+    mut camera_query: Query<&mut Transform>,
+) {
+    if effects.trauma <= 0.0 {
+        return;
+    }
+
+    let config = r!(config.get());
+    let mut transform = r!(camera_query.get_mut(camera_root.primary));
+    let mut rng = rand::thread_rng();
+    let direction = Vec2::new(rng.gen_range(-1.0..=1.0), rng.gen_range(-1.0..=1.0));
+    let shake = effects.trauma * effects.trauma * config.shake_offset * direction;
+    transform.translation += shake.round().extend(0.0);
+}
+
+fn apply_zoom_punch(
+    camera_root: Res<CameraRoot>,
+    effects: Res<CameraEffects>,
+    mut camera_query: Query<&mut OrthographicProjection>,
+) {
+    let mut projection = r!(camera_query.get_mut(camera_root.primary));
+    let scale = 1.0 / (1.0 + effects.punch);
+    if projection.scale != scale {
+        projection.scale = scale;
+    }
+}
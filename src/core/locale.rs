@@ -0,0 +1,352 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use bevy_common_assets::ron::RonAssetPlugin;
+use iyes_progress::prelude::*;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::core::settings::Settings;
+use crate::core::UpdateSet;
+use crate::ui::prelude::*;
+use crate::util::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.configure::<(ConfigHandle<LocaleConfig>, StringTable, LocalizedText)>();
+
+    app.add_systems(
+        Update,
+        select_locale
+            .in_set(UpdateSet::SyncEarly)
+            .run_if(resource_changed::<Settings>),
+    );
+}
+
+#[derive(Asset, Reflect, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LocaleConfig {
+    /// The first locale is used for keys missing from the active locale.
+    pub locales: Vec<LocaleInfo>,
+    #[serde(skip)]
+    active: usize,
+}
+
+impl Config for LocaleConfig {
+    const PATH: &'static str = "config/locale.ron";
+    const EXTENSION: &'static str = "locale.ron";
+
+    fn on_load(&mut self, world: &mut World) {
+        let asset_server = world.resource::<AssetServer>();
+        for locale in &mut self.locales {
+            locale.table = asset_server.load(&locale.table_path);
+        }
+        self.select(&world.resource::<Settings>().locale);
+    }
+
+    fn count_progress(&self, asset_server: &AssetServer) -> Progress {
+        let mut progress = true.into();
+        for locale in &self.locales {
+            progress += asset_server
+                .is_loaded_with_dependencies(&locale.table)
+                .into();
+        }
+        progress
+    }
+}
+
+impl LocaleConfig {
+    /// The locale selected in `Settings`, or `None` if there are no locales.
+    pub fn active(&self) -> Option<&LocaleInfo> {
+        self.locales.get(self.active)
+    }
+
+    /// The name of the locale after the active locale, wrapping around.
+    pub fn next_name(&self) -> Option<&str> {
+        let next = (self.active + 1).checked_rem(self.locales.len())?;
+        Some(&self.locales[next].name)
+    }
+
+    fn select(&mut self, name: &str) {
+        self.active = self.position(name);
+    }
+
+    fn position(&self, name: &str) -> usize {
+        self.locales
+            .iter()
+            .position(|x| x.name == name)
+            .unwrap_or(0)
+    }
+}
+
+fn select_locale(
+    settings: Res<Settings>,
+    handle: Option<Res<ConfigHandle<LocaleConfig>>>,
+    mut locales: ResMut<Assets<LocaleConfig>>,
+) {
+    let handle = rq!(handle);
+    let config = rq!(locales.get(&handle.0));
+    if config.position(&settings.locale) == config.active {
+        return;
+    }
+
+    r!(locales.get_mut(&handle.0)).select(&settings.locale);
+}
+
+#[derive(Reflect, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LocaleInfo {
+    /// The name shown in the language picker.
+    pub name: String,
+    #[serde(rename = "table")]
+    table_path: String,
+    #[serde(skip)]
+    pub table: Handle<StringTable>,
+}
+
+/// A table of localized strings by key.
+/// Strings can contain rich text tags and `{0}`, `{1}`, ... placeholders.
+#[derive(Asset, Reflect, Serialize, Deserialize, Default)]
+pub struct StringTable(pub HashMap<String, String>);
+
+impl Configure for StringTable {
+    fn configure(app: &mut App) {
+        app.add_plugins(RonAssetPlugin::<Self>::new(&["strings.ron"]));
+    }
+}
+
+/// Localized string lookup for the active locale.
+#[derive(SystemParam)]
+pub struct Locale<'w> {
+    config: ConfigRef<'w, LocaleConfig>,
+    tables: Res<'w, Assets<StringTable>>,
+}
+
+impl Locale<'_> {
+    /// Whether the fallback locale has finished loading.
+    pub fn is_loaded(&self) -> bool {
+        self.config
+            .get()
+            .and_then(|config| config.locales.first())
+            .is_some_and(|locale| self.tables.contains(&locale.table))
+    }
+
+    /// Look up a key in the active locale, falling back to the first locale.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        let config = self.config.get()?;
+        [config.active, 0]
+            .into_iter()
+            .filter_map(|i| config.locales.get(i))
+            .filter_map(|locale| self.tables.get(&locale.table))
+            .find_map(|table| table.0.get(key))
+            .map(String::as_str)
+    }
+
+    /// Look up a key and fill in its placeholders, or show the key itself if it's missing.
+    pub fn format(&self, key: &str, args: &[String]) -> String {
+        fill_placeholders(self.get(key).unwrap_or(key), args)
+    }
+
+    /// Parse rich text, localizing keywords and coloring keywords and icons.
+    pub fn parse_rich(&self, text: &str) -> Vec<(TextSection, RichSpan)> {
+        let mut sections = parse_rich_with_spans(text);
+        for (section, span) in &mut sections {
+            if let Some(keyword) = &span.keyword {
+                if let Some(name) = self.get(&format!("keyword.{keyword}.name")) {
+                    section.value = name.to_string();
+                }
+                span.color.get_or_insert(ThemeColor::Indicator);
+            } else if span.icon.is_some() {
+                span.color = Some(ThemeColor::Invisible);
+            }
+        }
+        sections
+    }
+}
+
+/// Replace `{0}`, `{1}`, ... in a template with the corresponding arguments.
+fn fill_placeholders(template: &str, args: &[String]) -> String {
+    let mut text = template.to_string();
+    for (i, arg) in args.iter().enumerate() {
+        text = text.replace(&format!("{{{i}}}"), arg);
+    }
+    text
+}
+
+/// Text that shows a localized string, updating when the locale changes.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct LocalizedText {
+    pub key: String,
+    pub args: Vec<String>,
+    /// Parse the string as rich text into all sections, instead of only setting the first section.
+    pub rich: bool,
+    /// The string that was last applied.
+    #[reflect(ignore)]
+    value: String,
+    /// The entity's `ThemeColorForText` before any color tags were applied.
+    #[reflect(ignore)]
+    base_colors: Option<Vec<ThemeColor>>,
+}
+
+impl Configure for LocalizedText {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(Update, apply_localized_text.in_set(UpdateSet::SyncLate));
+    }
+}
+
+impl LocalizedText {
+    pub fn new(key: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            ..default()
+        }
+    }
+
+    pub fn rich(key: impl Into<String>) -> Self {
+        Self {
+            rich: true,
+            ..Self::new(key)
+        }
+    }
+
+    pub fn with_args(mut self, args: impl IntoIterator<Item = impl ToString>) -> Self {
+        self.args = args.into_iter().map(|x| x.to_string()).collect();
+        self
+    }
+}
+
+impl From<&str> for LocalizedText {
+    fn from(key: &str) -> Self {
+        Self::new(key)
+    }
+}
+
+impl From<String> for LocalizedText {
+    fn from(key: String) -> Self {
+        Self::new(key)
+    }
+}
+
+fn apply_localized_text(
+    mut commands: Commands,
+    locale: Locale,
+    mut text_query: Query<(
+        Entity,
+        &mut LocalizedText,
+        &mut Text,
+        Option<&mut ThemeColorForText>,
+        Option<&InlineIcons>,
+        Option<&InlineKeywords>,
+    )>,
+) {
+    if !locale.is_loaded() {
+        return;
+    }
+
+    for (entity, mut localized, mut text, colors, old_icons, old_keywords) in &mut text_query {
+        let value = locale.format(&localized.key, &localized.args);
+        if value == localized.value {
+            continue;
+        }
+
+        if localized.rich {
+            let (sections, spans): (Vec<_>, Vec<_>) = locale.parse_rich(&value).into_iter().unzip();
+            text.sections = sections;
+
+            // Tagged sections use their tag color, and the rest keep the entity's colors by position.
+            if let Some(mut colors) = colors {
+                let base = localized
+                    .base_colors
+                    .get_or_insert_with(|| colors.0.clone());
+                colors.0 = if spans.iter().any(|span| span.color.is_some()) {
+                    spans
+                        .iter()
+                        .enumerate()
+                        .map(|(i, span)| {
+                            span.color
+                                .or_else(|| base.get(i).or(base.last()).copied())
+                                .unwrap_or(ThemeColor::BodyText)
+                        })
+                        .collect()
+                } else {
+                    base.clone()
+                };
+            }
+
+            let icons = InlineIcons::from_spans(&spans);
+            if old_icons.is_some() || !icons.0.is_empty() {
+                commands.entity(entity).insert(icons);
+            }
+            let keywords = InlineKeywords::from_spans(&spans);
+            if old_keywords.is_some() || !keywords.0.is_empty() {
+                commands.entity(entity).insert(keywords);
+            }
+        } else if let Some(section) = text.sections.first_mut() {
+            section.value.clone_from(&value);
+        }
+        localized.value = value;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::achievement::AchievementConfig;
+    use crate::game::actor::ActorConfig;
+    use crate::game::card::CardConfig;
+    use crate::game::challenge::ChallengeConfig;
+
+    fn table(ron: &str) -> StringTable {
+        ron::from_str(ron).unwrap()
+    }
+
+    #[test]
+    fn translations_cover_english() {
+        let en = table(include_str!("../../assets/locale/en.strings.ron"));
+        let es = table(include_str!("../../assets/locale/es.strings.ron"));
+        let mut missing =
+            en.0.keys()
+                .filter(|key| !es.0.contains_key(*key))
+                .collect::<Vec<_>>();
+        missing.sort();
+        assert!(missing.is_empty(), "missing from es: {missing:?}");
+    }
+
+    #[test]
+    fn translations_cover_configs() {
+        let cards: CardConfig =
+            ron::from_str(include_str!("../../assets/config/card.ron")).unwrap();
+        let actors: ActorConfig =
+            ron::from_str(include_str!("../../assets/config/actor.ron")).unwrap();
+        let achievements: AchievementConfig =
+            ron::from_str(include_str!("../../assets/config/achievements.ron")).unwrap();
+        let challenges: ChallengeConfig =
+            ron::from_str(include_str!("../../assets/config/challenge.ron")).unwrap();
+        let es = table(include_str!("../../assets/locale/es.strings.ron"));
+
+        let mut keys = vec![];
+        for key in cards.card_map.keys() {
+            keys.push(format!("card.{key}.name"));
+            keys.push(format!("card.{key}.description"));
+        }
+        for key in actors.players.keys().chain(actors.enemies.keys()) {
+            keys.push(format!("actor.{key}.name"));
+        }
+        for key in achievements.achievements.keys() {
+            keys.push(format!("achievement.{key}.name"));
+            keys.push(format!("achievement.{key}.description"));
+        }
+        for key in challenges.modifiers.keys() {
+            keys.push(format!("modifier.{key}.name"));
+            keys.push(format!("modifier.{key}.description"));
+        }
+        let mut missing = keys
+            .into_iter()
+            .filter(|key| !es.0.contains_key(key))
+            .collect::<Vec<_>>();
+        missing.sort();
+        missing.dedup();
+        assert!(missing.is_empty(), "missing from es: {missing:?}");
+    }
+}
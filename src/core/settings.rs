@@ -0,0 +1,60 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::util::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.configure::<Settings>();
+}
+
+/// Player preferences, saved between sessions.
+#[derive(Resource, Reflect, Serialize, Deserialize)]
+#[reflect(Resource)]
+#[serde(default)]
+pub struct Settings {
+    /// The delay (in seconds) between the music playing and the player hearing it.
+    pub audio_latency: f64,
+    pub music_volume: f64,
+    pub sfx_volume: f64,
+    pub ui_volume: f64,
+    pub voice_volume: f64,
+    /// Show floating numbers for damage and heals.
+    pub damage_numbers: bool,
+    /// Show the minimap in the HUD.
+    pub minimap: bool,
+    /// The name of the active theme in `ThemeConfig`.
+    pub theme: String,
+    /// The name of the active locale in `LocaleConfig`.
+    pub locale: String,
+    /// The key of the chosen player character in `ActorConfig`.
+    pub player: String,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            audio_latency: 0.0,
+            music_volume: 1.0,
+            sfx_volume: 1.0,
+            ui_volume: 1.0,
+            voice_volume: 1.0,
+            damage_numbers: true,
+            minimap: true,
+            theme: String::new(),
+            locale: String::new(),
+            player: String::new(),
+        }
+    }
+}
+
+impl Configure for Settings {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.configure::<Persistent<Self>>();
+    }
+}
+
+impl Persist for Settings {
+    const KEY: &'static str = "settings";
+}
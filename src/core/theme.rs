@@ -5,7 +5,9 @@ use bevy::prelude::*;
 use serde::Deserialize;
 use serde::Serialize;
 use strum::EnumCount;
+use strum::EnumString;
 
+use crate::core::settings::Settings;
 use crate::core::UpdateSet;
 use crate::util::prelude::*;
 
@@ -22,12 +24,22 @@ pub(super) fn plugin(app: &mut App) {
         ThemeColorFor<Outline>,
         ThemeColorForText,
     )>();
+
+    app.add_systems(
+        Update,
+        select_theme
+            .in_set(UpdateSet::SyncEarly)
+            .run_if(resource_changed::<Settings>),
+    );
 }
 
 #[derive(Asset, Reflect, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct ThemeConfig {
-    pub colors: ThemeColorList,
-    // TODO: pub fonts: ThemeFontList,
+    /// The first theme is used if the theme in `Settings` isn't found.
+    pub themes: Vec<Theme>,
+    #[serde(skip)]
+    active: usize,
 }
 
 impl Config for ThemeConfig {
@@ -35,10 +47,67 @@ impl Config for ThemeConfig {
     const EXTENSION: &'static str = "theme.ron";
 
     fn on_load(&mut self, world: &mut World) {
-        world.resource_mut::<ClearColor>().0 = self.colors[ThemeColor::Body];
+        self.select(&world.resource::<Settings>().theme);
+        let theme = r!(self.active());
+        world.resource_mut::<ClearColor>().0 = theme.colors[ThemeColor::Body];
+    }
+}
+
+impl ThemeConfig {
+    /// The theme selected in `Settings`, or `None` if there are no themes.
+    pub fn active(&self) -> Option<&Theme> {
+        self.themes.get(self.active)
+    }
+
+    /// The name of the theme after the active theme, wrapping around.
+    pub fn next_name(&self) -> Option<&str> {
+        let next = (self.active + 1).checked_rem(self.themes.len())?;
+        Some(&self.themes[next].name)
+    }
+
+    fn select(&mut self, name: &str) {
+        self.active = self.position(name);
+    }
+
+    fn position(&self, name: &str) -> usize {
+        self.themes.iter().position(|x| x.name == name).unwrap_or(0)
     }
 }
 
+fn select_theme(
+    settings: Res<Settings>,
+    handle: Option<Res<ConfigHandle<ThemeConfig>>>,
+    mut themes: ResMut<Assets<ThemeConfig>>,
+    mut clear_color: ResMut<ClearColor>,
+) {
+    let handle = rq!(handle);
+    let theme = rq!(themes.get(&handle.0));
+    if theme.position(&settings.theme) == theme.active {
+        return;
+    }
+
+    let theme = r!(themes.get_mut(&handle.0));
+    theme.select(&settings.theme);
+    clear_color.0 = r!(theme.active()).colors[ThemeColor::Body];
+}
+
+#[derive(Reflect, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Theme {
+    pub name: String,
+    pub colors: ThemeColorList,
+    /// Show a glyph on each card background, so card categories can be told apart without color.
+    #[serde(default)]
+    pub card_glyphs: bool,
+    /// Replaces `HealthConfig::color_ramp` if set.
+    #[serde(default)]
+    pub health_color_ramp: Option<Vec<Color>>,
+    /// Replaces `SpotlightConfig::color_loop` if set.
+    #[serde(default)]
+    pub spotlight_color_loop: Option<Vec<Color>>,
+    // TODO: pub fonts: ThemeFontList,
+}
+
 #[derive(Reflect, Serialize, Deserialize)]
 pub struct ThemeColorList([Color; ThemeColor::COUNT]);
 
@@ -51,7 +120,7 @@ impl Index<ThemeColor> for ThemeColorList {
 }
 
 /// See: <https://getbootstrap.com/docs/5.3/customize/color/>
-#[derive(Reflect, Clone, Copy, Default, EnumCount)]
+#[derive(Reflect, Eq, PartialEq, Clone, Copy, Default, Debug, EnumCount, EnumString)]
 pub enum ThemeColor {
     // Absolute colors
     #[default]
@@ -73,6 +142,13 @@ pub enum ThemeColor {
     Indicator,
     CardBorder,
     Overlay,
+
+    // Floating number colors
+    Damage,
+    PlayerDamage,
+    CritDamage,
+    Heal,
+    Immune,
 }
 
 impl ThemeColor {
@@ -102,7 +178,7 @@ fn apply_theme_color_for<C: Component + ColorMut>(
     theme: ConfigRef<ThemeConfig>,
     mut color_query: Query<(&ThemeColorFor<C>, &mut C)>,
 ) {
-    let palette = &r!(theme.get()).colors;
+    let palette = &r!(theme.get().and_then(ThemeConfig::active)).colors;
     for (theme_color, mut color) in &mut color_query {
         *color.color_mut() = palette[theme_color.0];
     }
@@ -126,7 +202,7 @@ fn apply_theme_color_for_text(
     theme: ConfigRef<ThemeConfig>,
     mut text_query: Query<(&ThemeColorForText, &mut Text)>,
 ) {
-    let palette = &r!(theme.get()).colors;
+    let palette = &r!(theme.get().and_then(ThemeConfig::active)).colors;
     for (colors, mut text) in &mut text_query {
         for (section, &color) in text.sections.iter_mut().zip(&colors.0) {
             section.style.color = palette[color];
@@ -1,14 +1,20 @@
 //! Game mechanics and content
 
+pub mod achievement;
 pub mod actor;
 pub mod audio;
 pub mod card;
+pub mod challenge;
 pub mod cleanup;
 pub mod combat;
 pub mod ground;
+pub mod impact;
+pub mod score;
 pub mod spotlight;
 pub mod sprite;
 pub mod stats;
+pub mod unlock;
+pub mod vfx;
 pub mod wave;
 
 use std::borrow::Cow;
@@ -25,15 +31,21 @@ pub(super) fn plugin(app: &mut App) {
     app.configure::<GameRoot>();
 
     app.add_plugins((
+        achievement::plugin,
         actor::plugin,
         audio::plugin,
         card::plugin,
+        challenge::plugin,
         cleanup::plugin,
         combat::plugin,
         ground::plugin,
+        impact::plugin,
+        score::plugin,
         spotlight::plugin,
         sprite::plugin,
         stats::plugin,
+        unlock::plugin,
+        vfx::plugin,
         wave::plugin,
     ));
 }
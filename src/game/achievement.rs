@@ -0,0 +1,362 @@
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use bevy::utils::HashSet;
+use pyri_state::prelude::*;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::core::UpdateSet;
+use crate::game::actor::faction::Faction;
+use crate::game::actor::level::up::LevelUp;
+use crate::game::actor::level::xp::OnXpReward;
+use crate::game::actor::player::IsPlayer;
+use crate::game::card::action::CardActionKey;
+use crate::game::card::CardConfig;
+use crate::game::combat::hit::OnHit;
+use crate::game::stats::Stats;
+use crate::screen::playing::PlayingMenu;
+use crate::screen::Screen;
+use crate::ui::prelude::*;
+use crate::util::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.configure::<(
+        ConfigHandle<AchievementConfig>,
+        Achievements,
+        AchievementTracker,
+    )>();
+}
+
+#[derive(Asset, Reflect, Serialize, Deserialize)]
+pub struct AchievementConfig {
+    pub achievements: HashMap<String, Achievement>,
+}
+
+impl Config for AchievementConfig {
+    const PATH: &'static str = "config/achievements.ron";
+    const EXTENSION: &'static str = "achievements.ron";
+}
+
+#[derive(Reflect, Serialize, Deserialize, Clone)]
+pub struct Achievement {
+    pub name: String,
+    pub description: String,
+    pub rule: AchievementRule,
+}
+
+impl Achievement {
+    /// The name in the active locale, or the name in `achievements.ron` if it's not translated.
+    pub fn localized_name(&self, key: &str, locale: &Locale) -> String {
+        locale
+            .get(&format!("achievement.{key}.name"))
+            .unwrap_or(&self.name)
+            .to_string()
+    }
+
+    /// The description in the active locale, or the description in `achievements.ron` if it's not translated.
+    pub fn localized_description(&self, key: &str, locale: &Locale) -> String {
+        locale
+            .get(&format!("achievement.{key}.description"))
+            .unwrap_or(&self.description)
+            .to_string()
+    }
+}
+
+/// The condition to earn an achievement.
+#[derive(Reflect, Serialize, Deserialize, Clone)]
+pub enum AchievementRule {
+    /// Count an event up to a target within a scope.
+    Reach {
+        event: AchievementEvent,
+        scope: AchievementScope,
+        target: f32,
+    },
+    /// Win a run with an event counted at most `max` times.
+    WinWithAtMost { event: AchievementEvent, max: f32 },
+}
+
+/// An event that an achievement rule counts.
+#[derive(Reflect, Serialize, Deserialize, Clone)]
+pub enum AchievementEvent {
+    /// The player kills an enemy (of a specific key, or any enemy if `None`).
+    Kill(Option<String>),
+    /// The player plays a card (of a specific key, or any card if `None`).
+    Play(Option<String>),
+    /// The player plays a card of a category.
+    PlayCategory(CardActionKey),
+    /// An enemy is hit.
+    Hit,
+    /// The player is hit.
+    Hurt,
+    /// The player earns XP (counted by the amount).
+    Xp,
+    /// The player levels up.
+    LevelUp,
+    /// The player wins a run.
+    Win,
+}
+
+impl AchievementEvent {
+    /// How much an occurrence counts towards this event.
+    fn count(&self, occurrence: &Occurrence) -> f32 {
+        match (self, occurrence) {
+            (Self::Kill(key), Occurrence::Kill(x)) | (Self::Play(key), Occurrence::Play(x, _))
+                if key.as_ref().map_or(true, |key| key == x) =>
+            {
+                1.0
+            },
+            (Self::PlayCategory(key), Occurrence::Play(_, x)) if key == x => 1.0,
+            (Self::Hit, Occurrence::Hit)
+            | (Self::Hurt, Occurrence::Hurt)
+            | (Self::LevelUp, Occurrence::LevelUp)
+            | (Self::Win, Occurrence::Win) => 1.0,
+            (Self::Xp, Occurrence::Xp(amount)) => *amount,
+            _ => 0.0,
+        }
+    }
+}
+
+/// How long an achievement rule keeps counting before it starts over.
+#[derive(Reflect, Serialize, Deserialize, Clone)]
+pub enum AchievementScope {
+    /// Across all runs.
+    Lifetime,
+    /// Within a single run.
+    Run,
+    /// Within a single play of a card, until the player plays their next card.
+    CardPlay(String),
+}
+
+/// Earned achievements and lifetime progress, saved between sessions.
+/// Progress is only saved at the end of a run, rather than on every change.
+#[derive(Resource, Reflect, Serialize, Deserialize, Default)]
+#[reflect(Resource)]
+#[serde(default)]
+pub struct Achievements {
+    pub earned: HashSet<String>,
+    /// Lifetime progress by achievement key.
+    pub progress: HashMap<String, f32>,
+}
+
+impl Configure for Achievements {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.configure::<Persistent<Self>>();
+        app.add_systems(
+            StateFlush,
+            (
+                PlayingMenu::Victory.on_enter(save_achievement_progress),
+                PlayingMenu::Defeat.on_enter(save_achievement_progress),
+                Screen::Playing.on_exit(save_achievement_progress),
+            ),
+        );
+    }
+}
+
+impl Persist for Achievements {
+    const KEY: &'static str = "achievements";
+}
+
+fn save_achievement_progress(mut achievements: ResMut<Achievements>) {
+    achievements.set_changed();
+}
+
+/// Something that happened during a run, for achievement rules to count.
+#[derive(Reflect)]
+enum Occurrence {
+    Kill(String),
+    Play(String, CardActionKey),
+    Hit,
+    Hurt,
+    Xp(f32),
+    LevelUp,
+    Win,
+}
+
+/// Achievement progress for the current run, and occurrences waiting to be counted.
+#[derive(Resource, Reflect, Default)]
+#[reflect(Resource)]
+struct AchievementTracker {
+    /// The kills in [`Stats`] that have been counted.
+    kills: HashMap<String, usize>,
+    /// The plays in [`Stats`] that have been counted.
+    plays: HashMap<String, usize>,
+    /// Run progress by achievement key.
+    run: HashMap<String, f32>,
+    /// Card play progress by achievement key.
+    card_play: HashMap<String, f32>,
+    /// The card the player played last.
+    last_played: Option<String>,
+    pending: Vec<Occurrence>,
+}
+
+impl Configure for AchievementTracker {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.init_resource::<Self>();
+        app.add_systems(
+            StateFlush,
+            (
+                Screen::Playing.on_enter(reset_achievement_tracker),
+                PlayingMenu::Victory.on_enter(track_win),
+            ),
+        );
+        app.add_systems(
+            Update,
+            (
+                track_level_ups.run_if(on_event::<LevelUp>()),
+                evaluate_achievements,
+            )
+                .chain()
+                .in_set(UpdateSet::SyncLate),
+        );
+        app.observe(track_hit);
+        app.observe(track_xp);
+    }
+}
+
+fn reset_achievement_tracker(mut tracker: ResMut<AchievementTracker>) {
+    *tracker = default();
+}
+
+fn track_win(mut tracker: ResMut<AchievementTracker>) {
+    tracker.pending.push(Occurrence::Win);
+}
+
+fn track_hit(
+    trigger: Trigger<OnHit>,
+    faction_query: Query<&Faction>,
+    mut tracker: ResMut<AchievementTracker>,
+) {
+    let faction = rq!(faction_query.get(trigger.event().1));
+    tracker.pending.push(if faction.is_player() {
+        Occurrence::Hurt
+    } else {
+        Occurrence::Hit
+    });
+}
+
+fn track_xp(trigger: Trigger<OnXpReward>, mut tracker: ResMut<AchievementTracker>) {
+    tracker.pending.push(Occurrence::Xp(trigger.event().0));
+}
+
+fn track_level_ups(
+    mut level_up_events: EventReader<LevelUp>,
+    player_query: Query<(), With<IsPlayer>>,
+    mut tracker: ResMut<AchievementTracker>,
+) {
+    for event in level_up_events.read() {
+        if player_query.contains(event.0) {
+            tracker.pending.push(Occurrence::LevelUp);
+        }
+    }
+}
+
+/// Occurrences for the counts in `now` that haven't been counted yet.
+fn new_occurrences(
+    counted: &mut HashMap<String, usize>,
+    now: &HashMap<String, usize>,
+    mut occurrence: impl FnMut(&str) -> Option<Occurrence>,
+) -> Vec<Occurrence> {
+    let mut occurrences = vec![];
+    for (key, &count) in now {
+        let counted = counted.entry(key.clone()).or_default();
+        for _ in *counted..count {
+            occurrences.extend(occurrence(key));
+        }
+        *counted = count;
+    }
+    occurrences
+}
+
+fn evaluate_achievements(
+    mut commands: Commands,
+    config: ConfigRef<AchievementConfig>,
+    card_config: ConfigRef<CardConfig>,
+    stats: Res<Stats>,
+    mut achievements: ResMut<Achievements>,
+    mut tracker: ResMut<AchievementTracker>,
+    locale: Locale,
+) {
+    if tracker.pending.is_empty() && !stats.is_changed() {
+        return;
+    }
+    let config = r!(config.get());
+    let card_config = r!(card_config.get());
+    // Avoid triggering a save until an achievement is earned.
+    let state = achievements.bypass_change_detection();
+    let tracker = &mut *tracker;
+
+    // Cards are played before anything else happens in a frame, and enemies die after they're hit.
+    let plays = new_occurrences(&mut tracker.plays, &stats.plays, |key| {
+        let card = card_config.card_map.get(key)?;
+        Some(Occurrence::Play(key.to_string(), card.action_key))
+    });
+    let kills = new_occurrences(&mut tracker.kills, &stats.kills, |key| {
+        Some(Occurrence::Kill(key.to_string()))
+    });
+    let pending = std::mem::take(&mut tracker.pending);
+
+    let mut earned = vec![];
+    for occurrence in plays.into_iter().chain(pending).chain(kills) {
+        if let Occurrence::Play(key, _) = &occurrence {
+            tracker.card_play.clear();
+            tracker.last_played = Some(key.clone());
+        }
+
+        for (key, achievement) in &config.achievements {
+            if state.earned.contains(key) {
+                continue;
+            }
+
+            let done = match &achievement.rule {
+                AchievementRule::Reach {
+                    event,
+                    scope,
+                    target,
+                } => {
+                    let count = event.count(&occurrence);
+                    if count == 0.0 {
+                        continue;
+                    }
+                    let progress = match scope {
+                        AchievementScope::Lifetime => &mut state.progress,
+                        AchievementScope::Run => &mut tracker.run,
+                        AchievementScope::CardPlay(card_key) => {
+                            if tracker.last_played.as_ref() != Some(card_key) {
+                                continue;
+                            }
+                            &mut tracker.card_play
+                        },
+                    }
+                    .entry(key.clone())
+                    .or_default();
+                    *progress += count;
+                    *progress >= *target
+                },
+                AchievementRule::WinWithAtMost { event, max } => {
+                    let progress = tracker.run.entry(key.clone()).or_default();
+                    *progress += event.count(&occurrence);
+                    matches!(occurrence, Occurrence::Win) && *progress <= *max
+                },
+            };
+            if done {
+                state.earned.insert(key.clone());
+                earned.push((
+                    achievement.localized_name(key, &locale),
+                    achievement.localized_description(key, &locale),
+                ));
+            }
+        }
+    }
+
+    if earned.is_empty() {
+        return;
+    }
+    achievements.set_changed();
+    for (name, description) in earned {
+        commands.spawn_with(toast(
+            LocalizedText::rich("achievement.toast").with_args([name, description]),
+        ));
+    }
+}
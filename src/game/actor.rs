@@ -20,6 +20,11 @@ use serde::Deserialize;
 use serde::Serialize;
 use shield::IsShield;
 
+use crate::animation::backup::Backup;
+use crate::animation::pulse::OnBeatPulse;
+use crate::animation::pulse::PulseColorFor;
+use crate::core::locale::Locale;
+use crate::core::theme::ThemeColor;
 use crate::game::actor::attack::Attack;
 use crate::game::actor::attack::AttackController;
 use crate::game::actor::facing::Facing;
@@ -33,8 +38,11 @@ use crate::game::actor::movement::MovementController;
 use crate::game::actor::movement::OldMovementController;
 use crate::game::audio::music::Beat;
 use crate::game::card::deck::Deck;
+use crate::game::combat::death::DeathAnimation;
 use crate::game::combat::hit::Hurtbox;
 use crate::game::sprite::SpriteAnimation;
+use crate::game::unlock::Unlock;
+use crate::game::unlock::Unlocks;
 use crate::screen::playing::PlayingAssets;
 use crate::util::prelude::*;
 
@@ -60,6 +68,28 @@ pub struct ActorConfig {
     pub enemies: HashMap<String, Actor>,
 }
 
+impl ActorConfig {
+    /// The keys of the unlocked player characters, sorted by key.
+    pub fn unlocked_players(&self, unlocks: &Unlocks) -> Vec<&str> {
+        let mut keys = self
+            .players
+            .iter()
+            .filter(|(key, actor)| unlocks.has_actor(key, actor))
+            .map(|(key, _)| key.as_str())
+            .collect::<Vec<_>>();
+        keys.sort_unstable();
+        keys
+    }
+
+    /// The chosen player character, or the first unlocked one if it's locked or missing.
+    pub fn player_key<'a>(&'a self, chosen: &'a str, unlocks: &Unlocks) -> Option<&'a str> {
+        let keys = self.unlocked_players(unlocks);
+        keys.contains(&chosen)
+            .then_some(chosen)
+            .or_else(|| keys.first().copied())
+    }
+}
+
 impl Config for ActorConfig {
     const PATH: &'static str = "config/actor.ron";
     const EXTENSION: &'static str = "actor.ron";
@@ -101,6 +131,9 @@ pub struct Actor {
     /// The relative probability of this actor spawning as an enemy.
     #[serde(default = "one")]
     pub weight: f64,
+    /// The condition to unlock this actor, or `None` if it's always unlocked.
+    #[serde(default)]
+    pub unlock: Option<Unlock>,
 
     #[serde(rename = "texture")]
     texture_path: String,
@@ -121,6 +154,18 @@ pub struct Actor {
     pub xp_reward: XpReward,
     #[serde(default)]
     pub deck: Deck,
+    #[serde(default)]
+    pub death_animation: DeathAnimation,
+}
+
+impl Actor {
+    /// The name in the active locale, or the name in `actor.ron` if it's not translated.
+    pub fn localized_name(&self, key: &str, locale: &Locale) -> String {
+        locale
+            .get(&format!("actor.{key}.name"))
+            .unwrap_or(&self.name)
+            .to_string()
+    }
 }
 
 fn inf() -> usize {
@@ -152,6 +197,8 @@ impl EntityCommand for Actor {
                     },
                     self.sprite_animation,
                     Facing::default(),
+                    PulseColorFor::<Sprite>::new(OnBeatPulse::new(8), ThemeColor::Indicator, 0.15),
+                    Backup::<Sprite>::default(),
                 ),
                 // Physics:
                 (
@@ -168,7 +215,7 @@ impl EntityCommand for Actor {
                     AttackController::default(),
                     self.health,
                     Hurtbox,
-                    // TODO: Death animation.
+                    self.death_animation,
                 ),
                 // Inventory:
                 (Level::default(), Xp::default(), self.xp_reward, self.deck),
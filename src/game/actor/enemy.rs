@@ -1,8 +1,12 @@
+pub mod bestiary;
+pub mod indicator;
+
 use avian2d::prelude::*;
 use bevy::ecs::system::EntityCommand;
 use bevy::ecs::system::SystemState;
 use bevy::prelude::*;
 
+use crate::game::actor::enemy::bestiary::Bestiary;
 use crate::game::actor::facing::FacePlayer;
 use crate::game::actor::faction::Faction;
 use crate::game::actor::ActorConfig;
@@ -14,7 +18,9 @@ use crate::screen::playing::PlayingAssets;
 use crate::util::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
-    app.configure::<IsEnemy>();
+    app.configure::<(IsEnemy, EnemyKey)>();
+
+    app.add_plugins((bestiary::plugin, indicator::plugin));
 }
 
 #[derive(Component, Reflect, Default)]
@@ -27,6 +33,17 @@ impl Configure for IsEnemy {
     }
 }
 
+/// The key of the enemy in `ActorConfig::enemies`.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct EnemyKey(pub String);
+
+impl Configure for EnemyKey {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+    }
+}
+
 pub fn enemy(key: impl Into<String>) -> impl EntityCommand {
     let key = key.into();
     move |entity: Entity, world: &mut World| {
@@ -47,13 +64,14 @@ pub fn enemy(key: impl Into<String>) -> impl EntityCommand {
             .add(actor)
             .insert((
                 IsEnemy,
+                EnemyKey(key.clone()),
                 Faction::Enemy,
                 CollisionLayers::new(GameLayer::Enemy, LayerMask::ALL),
                 FacePlayer,
                 DeathSfx(sfx_death, 0.5),
-                // TODO: Despawn when death animation is finished, instead.
                 DespawnOnDeath,
             ))
             .set_parent(parent);
+        see(&mut world.resource_mut::<Bestiary>(), &key);
     }
 }
@@ -0,0 +1,45 @@
+use std::ops::Deref;
+use std::ops::DerefMut;
+
+use bevy::prelude::*;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::util::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.configure::<Bestiary>();
+}
+
+/// The enemies the player has encountered, saved between sessions.
+#[derive(Resource, Reflect, Serialize, Deserialize, Default)]
+#[reflect(Resource)]
+#[serde(default)]
+pub struct Bestiary {
+    seen: SeenSet,
+}
+
+impl Configure for Bestiary {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.configure::<Persistent<Self>>();
+    }
+}
+
+impl Persist for Bestiary {
+    const KEY: &'static str = "bestiary";
+}
+
+impl Deref for Bestiary {
+    type Target = SeenSet;
+
+    fn deref(&self) -> &Self::Target {
+        &self.seen
+    }
+}
+
+impl DerefMut for Bestiary {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.seen
+    }
+}
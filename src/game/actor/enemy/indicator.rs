@@ -0,0 +1,114 @@
+use bevy::ecs::system::EntityCommand;
+use bevy::prelude::*;
+
+use crate::core::camera::CameraRoot;
+use crate::core::theme::ThemeColor;
+use crate::core::UpdateSet;
+use crate::game::actor::enemy::IsEnemy;
+use crate::game::combat::death::IsDead;
+use crate::game::GameRoot;
+use crate::screen::playing::hud::HudConfig;
+use crate::screen::playing::PlayingAssets;
+use crate::util::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.configure::<OffscreenIndicator>();
+}
+
+/// An arrow at the edge of the screen pointing towards an off-screen enemy.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct OffscreenIndicator;
+
+impl Configure for OffscreenIndicator {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(
+            Update,
+            (
+                spawn_offscreen_indicators.in_set(UpdateSet::Spawn),
+                update_offscreen_indicators.in_set(UpdateSet::SyncLate),
+            ),
+        );
+    }
+}
+
+fn offscreen_indicator(target: Entity) -> impl EntityCommand<World> {
+    move |mut entity: EntityWorldMut| {
+        let texture = entity
+            .world()
+            .resource::<PlayingAssets>()
+            .mini_arrow
+            .clone();
+
+        entity.insert((
+            Name::new("OffscreenIndicator"),
+            SpriteBundle {
+                texture,
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+            ThemeColor::Indicator.target::<Sprite>(),
+            Selection(target),
+            OffscreenIndicator,
+        ));
+    }
+}
+
+fn spawn_offscreen_indicators(
+    mut commands: Commands,
+    game_root: Res<GameRoot>,
+    enemy_query: Query<Entity, Added<IsEnemy>>,
+) {
+    for enemy in &enemy_query {
+        commands
+            .spawn_with(offscreen_indicator(enemy))
+            .set_parent(game_root.vfx);
+    }
+}
+
+fn update_offscreen_indicators(
+    config: ConfigRef<HudConfig>,
+    camera_root: Res<CameraRoot>,
+    camera_query: Query<(&Transform, &OrthographicProjection), Without<OffscreenIndicator>>,
+    target_query: Query<(&GlobalTransform, Has<IsDead>)>,
+    mut despawn: ResMut<LateDespawn>,
+    mut indicator_query: Query<
+        (Entity, &Selection, &mut Transform, &mut Visibility),
+        With<OffscreenIndicator>,
+    >,
+) {
+    let config = r!(config.get());
+    let (camera_transform, projection) = r!(camera_query.get(camera_root.primary));
+    let center = camera_transform.translation.xy();
+    let half_size = projection.area.half_size();
+    let edge = (half_size - config.indicator_margin).max(Vec2::ZERO);
+
+    for (entity, selection, mut transform, mut visibility) in &mut indicator_query {
+        let Ok((target, is_dead)) = target_query.get(selection.0) else {
+            despawn.recursive(entity);
+            continue;
+        };
+
+        let delta = target.translation().xy() - center;
+        if is_dead || delta.abs().cmple(half_size).all() {
+            *visibility = Visibility::Hidden;
+            continue;
+        }
+        *visibility = Visibility::Inherited;
+
+        // Clamp the target's position to the edge of the screen.
+        let t = (edge / delta.abs()).min_element();
+        transform.translation = (center + t * delta).extend(transform.translation.z);
+        transform.rotation = Quat::from_rotation_z(delta.to_angle());
+
+        let scale = if config.indicator_scale_by_distance {
+            let far = (delta.length() * (1.0 - t) / config.indicator_far_distance).clamp(0.0, 1.0);
+            config.indicator_scale_near
+                + (config.indicator_scale_far - config.indicator_scale_near) * far
+        } else {
+            1.0
+        };
+        transform.scale = Vec3::splat(scale);
+    }
+}
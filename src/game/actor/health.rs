@@ -3,6 +3,8 @@ use bevy::prelude::*;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::core::theme::Theme;
+use crate::core::theme::ThemeConfig;
 use crate::core::UpdateSet;
 use crate::game::combat::damage::OnDamage;
 use crate::game::combat::death::IsDead;
@@ -24,21 +26,26 @@ impl Config for HealthConfig {
 }
 
 impl HealthConfig {
-    fn color(&self, t: f32) -> Color {
-        let n = self.color_ramp.len();
+    fn color(&self, theme: &Theme, t: f32) -> Color {
+        let color_ramp = theme.health_color_ramp.as_ref().unwrap_or(&self.color_ramp);
+        let n = color_ramp.len();
         let t = t * (n - 1) as f32;
         let lo = t as usize;
         let hi = lo + 1;
         let t = t.fract();
 
         if hi >= n {
-            self.color_ramp[n - 1]
+            color_ramp[n - 1]
         } else {
-            self.color_ramp[lo].mix(&self.color_ramp[hi], t)
+            color_ramp[lo].mix(&color_ramp[hi], t)
         }
     }
 }
 
+/// An observable event triggered when an actor is healed.
+#[derive(Event)]
+pub struct OnHeal(pub f32);
+
 #[derive(Component, Reflect, Serialize, Deserialize, Copy, Clone)]
 #[reflect(Component)]
 pub struct Health {
@@ -100,17 +107,19 @@ impl Configure for HealthBar {
 
 fn update_health_bar(
     config: ConfigRef<HealthConfig>,
+    theme: ConfigRef<ThemeConfig>,
     health_query: Query<&Health>,
     mut health_bar_query: Query<(&HealthBar, &Parent, &mut Sprite)>,
 ) {
     let config = r!(config.get());
+    let theme = r!(theme.get().and_then(ThemeConfig::active));
 
     for (health_bar, parent, mut sprite) in &mut health_bar_query {
         let health = c!(health_query.get(parent.get()));
         let t = health.current / health.max;
 
         sprite.custom_size = Some(Vec2::new(t * health_bar.size.x, health_bar.size.y));
-        sprite.color = config.color(t);
+        sprite.color = config.color(theme, t);
     }
 }
 
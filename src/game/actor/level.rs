@@ -18,6 +18,10 @@ pub(super) fn plugin(app: &mut App) {
 pub struct LevelConfig {
     /// The level sequence (final level repeats forever).
     pub levels: Vec<LevelData>,
+    /// The bonus XP multiplier per combo.
+    pub combo_xp_bonus: f32,
+    /// The maximum bonus XP multiplier from combos.
+    pub combo_xp_cap: f32,
 }
 
 impl Config for LevelConfig {
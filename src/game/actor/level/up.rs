@@ -8,6 +8,7 @@ use crate::game::actor::level::xp::Xp;
 use crate::game::actor::level::Level;
 use crate::game::actor::level::LevelConfig;
 use crate::game::actor::player::IsPlayer;
+use crate::game::audio::mixer::SfxChannel;
 use crate::screen::playing::victory_menu::EndlessMode;
 use crate::screen::playing::PlayingAssets;
 use crate::screen::playing::PlayingMenu;
@@ -19,7 +20,7 @@ pub(super) fn plugin(app: &mut App) {
 
 /// A buffered event sent when an actor levels up.
 #[derive(Event)]
-pub struct LevelUp(#[allow(unused)] Entity);
+pub struct LevelUp(pub Entity);
 
 impl Configure for LevelUp {
     fn configure(app: &mut App) {
@@ -70,7 +71,7 @@ fn trigger_level_up(
     mut level_query: Query<(Entity, &mut Level)>,
     endless_mode: Res<EndlessMode>,
     mut playing_menu: NextMut<PlayingMenu>,
-    audio: Res<Audio>,
+    audio: Res<AudioChannel<SfxChannel>>,
     assets: Res<PlayingAssets>,
 ) {
     for (entity, mut level) in &mut level_query {
@@ -89,7 +90,7 @@ fn trigger_level_up(
     }
 }
 
-fn play_level_up_sfx(audio: Res<Audio>, assets: Res<PlayingAssets>) {
+fn play_level_up_sfx(audio: Res<AudioChannel<SfxChannel>>, assets: Res<PlayingAssets>) {
     audio.play(assets.sfx_level_up.clone()).with_volume(0.8);
 }
 
@@ -7,6 +7,7 @@ use crate::game::actor::faction::Faction;
 use crate::game::actor::level::Level;
 use crate::game::actor::level::LevelConfig;
 use crate::game::actor::player::IsPlayer;
+use crate::game::audio::timing::Combo;
 use crate::game::combat::death::OnDeath;
 use crate::ui::prelude::*;
 use crate::util::prelude::*;
@@ -51,9 +52,16 @@ impl Configure for OnXpReward {
 // TODO: Not needed for this jam game, but it would be "more correct" to track
 //       the owner of the projectile that killed the actor with the `XpReward`,
 //       and only trigger `OnXpReward` for that entity.
-fn receive_xp(trigger: Trigger<OnXpReward>, mut xp_query: Query<&mut Xp, With<IsPlayer>>) {
+fn receive_xp(
+    trigger: Trigger<OnXpReward>,
+    config: ConfigRef<LevelConfig>,
+    combo: Res<Combo>,
+    mut xp_query: Query<&mut Xp, With<IsPlayer>>,
+) {
+    let config = r!(config.get());
+    let amount = trigger.event().0 * combo.xp_multiplier(config);
     for mut xp in &mut xp_query {
-        xp.gain(trigger.event().0);
+        xp.gain(amount);
     }
 }
 
@@ -13,6 +13,10 @@ use crate::game::actor::facing::FacingIndicator;
 use crate::game::actor::faction::Faction;
 use crate::game::actor::movement::input::movement_action;
 use crate::game::actor::ActorConfig;
+use crate::game::card::perform::perform_action;
+use crate::game::card::perform::Performer;
+use crate::game::card::perform::PerformerMode;
+use crate::game::challenge::RunModifiers;
 use crate::game::combat::death::DeathSfx;
 use crate::game::combat::hit::Hitbox;
 use crate::game::combat::hit::HurtSfx;
@@ -23,7 +27,7 @@ use crate::screen::playing::PlayingAssets;
 use crate::util::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
-    app.configure::<IsPlayer>();
+    app.configure::<(IsPlayer, PlayerKey)>();
 }
 
 #[derive(Component, Reflect, Default)]
@@ -36,11 +40,22 @@ impl Configure for IsPlayer {
     }
 }
 
+/// The key of the player in `ActorConfig::players`.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct PlayerKey(pub String);
+
+impl Configure for PlayerKey {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+    }
+}
+
 pub fn player(key: impl Into<String>) -> impl EntityCommand {
     let key = key.into();
 
     move |entity: Entity, world: &mut World| {
-        let (actor, parent, camera, sfx_hurt, sfx_death) = {
+        let (mut actor, parent, camera, sfx_hurt, sfx_death) = {
             let (config, game_root, camera_root, assets) = SystemState::<(
                 ConfigRef<ActorConfig>,
                 Res<GameRoot>,
@@ -59,12 +74,16 @@ pub fn player(key: impl Into<String>) -> impl EntityCommand {
                 assets.sfx_restart.clone(),
             )
         };
+        world
+            .resource::<RunModifiers>()
+            .modify_deck(&mut actor.deck);
 
         world
             .entity_mut(entity)
             .add(actor)
             .insert((
                 IsPlayer,
+                PlayerKey(key),
                 Faction::Player,
                 CollisionLayers::new(GameLayer::Player, LayerMask::ALL),
                 ColliderDensity(5.0),
@@ -84,6 +103,13 @@ pub fn player(key: impl Into<String>) -> impl EntityCommand {
                     .insert(Transform::from_translation(vec3(0.0, -0.5, 2.0)));
             });
 
+        if world.resource::<PerformerMode>().0 {
+            world
+                .entity_mut(entity)
+                .add(perform_action)
+                .insert(Performer::default());
+        }
+
         // Allow manual movement / attack input in dev builds.
         #[cfg(feature = "dev")]
         world
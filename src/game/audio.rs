@@ -1,32 +1,75 @@
+pub mod mixer;
 pub mod music;
+pub mod sfx;
+pub mod stems;
+pub mod timing;
 
 use bevy::prelude::*;
 use bevy_kira_audio::prelude::*;
+use iyes_progress::prelude::*;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::game::audio::mixer::MusicChannel;
 use crate::game::audio::music::MusicHandle;
-use crate::screen::playing::PlayingAssets;
+use crate::game::audio::sfx::SfxStyle;
+use crate::game::audio::stems::MusicStem;
 use crate::util::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
     app.configure::<ConfigHandle<AudioConfig>>();
 
-    app.add_plugins(music::plugin);
+    app.add_plugins((
+        mixer::plugin,
+        music::plugin,
+        sfx::plugin,
+        stems::plugin,
+        timing::plugin,
+    ));
 }
 
 #[derive(Asset, Reflect, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct AudioConfig {
     pub global_volume: f64,
+    /// The music volume multiplier while a menu is ducking it.
+    pub music_duck: f64,
+    /// The duration (in seconds) of the fade into and out of ducking.
+    pub music_duck_secs: f64,
 
     pub music_volume: f64,
-    /// The precise beats-per-minute of the music.
-    pub music_bpm: f64,
-    /// The position (in seconds) of the zeroth beat.
-    pub music_zeroth_beat: f64,
-    pub music_loop_start: f64,
-    pub music_loop_end: f64,
+    /// The duration (in beats) of the crossfade between music tracks.
+    pub music_crossfade_beats: f64,
+    /// The music tracks, in order of increasing `min_level`, with boss tracks in any position.
+    pub tracks: Vec<MusicTrack>,
+
+    /// The notes (in semitones above the music's key) that in-key sound effects can play.
+    pub sfx_scale: Vec<i32>,
+    /// The maximum number of copies of a sound effect that can play at once.
+    pub sfx_voice_cap: usize,
+    pub hurt_sfx: SfxStyle,
+    pub death_sfx: SfxStyle,
+
+    /// The maximum timing error (in seconds) for a "perfect" input or card performance.
+    /// Timing is measured against the beat as it's heard, after `Settings::audio_latency`.
+    pub timing_perfect: f64,
+    /// The maximum timing error (in seconds) for a "good" input or card performance.
+    pub timing_good: f64,
+}
+
+impl AudioConfig {
+    /// The index of the music track to play at the given player level.
+    pub fn track_for_level(&self, level: usize) -> usize {
+        self.tracks
+            .iter()
+            .rposition(|track| !track.boss && track.min_level <= level)
+            .unwrap_or_default()
+    }
+
+    /// The index of the music track to play during a boss wave, if there is one.
+    pub fn boss_track(&self) -> Option<usize> {
+        self.tracks.iter().position(|track| track.boss)
+    }
 }
 
 impl Config for AudioConfig {
@@ -34,21 +77,119 @@ impl Config for AudioConfig {
     const EXTENSION: &'static str = "audio.ron";
 
     fn on_load(&mut self, world: &mut World) {
-        world.resource::<Audio>().set_volume(self.global_volume);
+        let asset_server = world.resource::<AssetServer>();
+        for track in &mut self.tracks {
+            track.audio = asset_server.load(&track.audio_path);
+            for stem in &mut track.stems {
+                stem.audio = asset_server.load(&stem.audio_path);
+            }
+        }
 
+        let track = r!(self.tracks.first());
         if !world
             .resource::<Assets<AudioInstance>>()
-            .contains(&world.resource::<MusicHandle>().0)
+            .contains(&world.resource::<MusicHandle>().instance)
         {
-            let music_handle = world
-                .resource::<Audio>()
-                .play(world.resource::<PlayingAssets>().music.clone())
-                .with_volume(self.music_volume)
-                .loop_from(self.music_loop_start)
-                .loop_until(self.music_loop_end)
+            let instance = world
+                .resource::<AudioChannel<MusicChannel>>()
+                .play(track.audio.clone())
+                .with_volume(self.music_volume * track.volume)
+                .loop_from(track.loop_start)
+                .loop_until(track.loop_end)
                 .paused()
                 .handle();
-            world.resource_mut::<MusicHandle>().0 = music_handle;
+            *world.resource_mut::<MusicHandle>() = MusicHandle { instance, track: 0 };
         }
     }
+
+    fn count_progress(&self, asset_server: &AssetServer) -> Progress {
+        let mut progress = true.into();
+
+        for track in &self.tracks {
+            progress += asset_server
+                .is_loaded_with_dependencies(&track.audio)
+                .into();
+            for stem in &track.stems {
+                progress += asset_server.is_loaded_with_dependencies(&stem.audio).into();
+            }
+        }
+
+        progress
+    }
+}
+
+#[derive(Reflect, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MusicTrack {
+    #[serde(rename = "audio")]
+    pub audio_path: String,
+    #[serde(skip)]
+    pub audio: Handle<AudioSource>,
+    #[serde(default = "one")]
+    pub volume: f64,
+
+    /// The precise beats-per-minute at the start of the track.
+    pub bpm: f64,
+    /// The position (in seconds) of the zeroth beat.
+    pub zeroth_beat: f64,
+    pub loop_start: f64,
+    pub loop_end: f64,
+    /// Changes in tempo partway through the track, in order of position.
+    #[serde(default)]
+    pub tempo_changes: Vec<TempoChange>,
+    /// The key (in semitones relative to the sound effects' original pitch).
+    #[serde(default)]
+    pub key: i32,
+    /// Layers played in sync with the track and mixed in by game intensity.
+    #[serde(default)]
+    pub stems: Vec<MusicStem>,
+
+    /// The minimum player level for this track to play.
+    #[serde(default)]
+    pub min_level: usize,
+    /// Whether this track plays during boss waves instead of by level.
+    #[serde(default)]
+    pub boss: bool,
+}
+
+impl MusicTrack {
+    /// The precise number of eighth-beats that occur in the track before a position.
+    pub fn beats_at(&self, position: f64) -> f64 {
+        let mut beats = 0.0;
+        let mut start = self.zeroth_beat;
+        let mut bpm = self.bpm;
+        for change in &self.tempo_changes {
+            if position < change.position {
+                break;
+            }
+
+            beats += (change.position - start).max(0.0) * bpm * 8.0 / 60.0;
+            start = start.max(change.position);
+            bpm = change.bpm;
+        }
+
+        beats + (position - start).max(0.0) * bpm * 8.0 / 60.0
+    }
+
+    /// The beats-per-minute at a position.
+    pub fn bpm_at(&self, position: f64) -> f64 {
+        self.tempo_changes
+            .iter()
+            .take_while(|change| change.position <= position)
+            .last()
+            .map_or(self.bpm, |change| change.bpm)
+    }
+}
+
+#[derive(Reflect, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TempoChange {
+    /// The position (in seconds) of the change.
+    pub position: f64,
+    /// The precise beats-per-minute from this position onwards.
+    pub bpm: f64,
+}
+
+fn one() -> f64 {
+    1.0
 }
@@ -0,0 +1,80 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_kira_audio::prelude::*;
+use pyri_state::prelude::*;
+
+use crate::core::settings::Settings;
+use crate::core::UpdateSet;
+use crate::game::audio::AudioConfig;
+use crate::screen::playing::PlayingMenu;
+use crate::util::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_audio_channel::<MusicChannel>();
+    app.add_audio_channel::<SfxChannel>();
+    app.add_audio_channel::<UiChannel>();
+    app.add_audio_channel::<VoiceChannel>();
+
+    app.add_systems(Update, update_channel_volumes.in_set(UpdateSet::SyncLate));
+}
+
+/// The audio channel for music tracks and their stems.
+#[derive(Resource)]
+pub struct MusicChannel;
+
+/// The audio channel for gameplay sound effects.
+#[derive(Resource)]
+pub struct SfxChannel;
+
+/// The audio channel for menu and button sound effects.
+#[derive(Resource)]
+pub struct UiChannel;
+
+/// The audio channel for actor hurt and death sounds.
+#[derive(Resource)]
+pub struct VoiceChannel;
+
+fn update_channel_volumes(
+    config: ConfigRef<AudioConfig>,
+    settings: Res<Settings>,
+    playing_menu: CurrentRef<PlayingMenu>,
+    music: Res<AudioChannel<MusicChannel>>,
+    sfx: Res<AudioChannel<SfxChannel>>,
+    ui: Res<AudioChannel<UiChannel>>,
+    voice: Res<AudioChannel<VoiceChannel>>,
+    mut last_volumes: Local<Option<[f64; 4]>>,
+) {
+    let config = r!(config.get());
+    // Duck the music under the in-game menus and level-up fanfare.
+    let duck = if [
+        PlayingMenu::Pause,
+        PlayingMenu::Deck,
+        PlayingMenu::LevelUp,
+        PlayingMenu::Victory,
+        PlayingMenu::Defeat,
+    ]
+    .iter()
+    .any(|menu| playing_menu.is_in(menu))
+    {
+        config.music_duck
+    } else {
+        1.0
+    };
+    let volumes = [
+        config.global_volume * settings.music_volume * duck,
+        config.global_volume * settings.sfx_volume,
+        config.global_volume * settings.ui_volume,
+        config.global_volume * settings.voice_volume,
+    ];
+    if *last_volumes == Some(volumes) {
+        return;
+    }
+    *last_volumes = Some(volumes);
+
+    let tween = AudioTween::linear(Duration::from_secs_f64(config.music_duck_secs));
+    music.set_volume(volumes[0]).fade_in(tween);
+    sfx.set_volume(volumes[1]);
+    ui.set_volume(volumes[2]);
+    voice.set_volume(volumes[3]);
+}
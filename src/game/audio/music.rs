@@ -1,11 +1,21 @@
+use std::time::Duration;
+
 use bevy::prelude::*;
 use bevy_kira_audio::prelude::*;
 use pyri_state::prelude::*;
 
 use crate::core::pause::Pause;
+use crate::core::settings::Settings;
 use crate::core::UpdateSet;
+use crate::game::actor::level::Level;
+use crate::game::actor::player::IsPlayer;
+use crate::game::audio::mixer::MusicChannel;
+use crate::game::audio::stems::Stems;
 use crate::game::audio::AudioConfig;
-use crate::screen::playing::PlayingAssets;
+use crate::game::audio::MusicTrack;
+use crate::game::challenge::RunModifiers;
+use crate::game::combat::death::IsDead;
+use crate::game::wave::IsBoss;
 use crate::screen::Screen;
 use crate::util::prelude::*;
 
@@ -15,18 +25,22 @@ pub(super) fn plugin(app: &mut App) {
 
 #[derive(Resource, Reflect, Default)]
 #[reflect(Resource)]
-pub struct MusicHandle(pub Handle<AudioInstance>);
+pub struct MusicHandle {
+    pub instance: Handle<AudioInstance>,
+    /// The index of the music track that's playing.
+    pub track: usize,
+}
 
 impl Configure for MusicHandle {
     fn configure(app: &mut App) {
         app.register_type::<Self>();
         app.init_resource::<Self>();
         app.add_systems(
-            StateFlush,
-            Pause.on_edge(
-                // TODO: Unfortunate that this run condition is necessary...
-                unpause_music.run_if(Screen::Playing.will_enter()),
-                pause_music,
+            Update,
+            Screen::Playing.on_update(
+                switch_music_track
+                    .in_set(UpdateSet::Update)
+                    .run_if(on_full_beat(1)),
             ),
         );
     }
@@ -36,42 +50,118 @@ pub fn stop_music(
     music_handle: Res<MusicHandle>,
     mut audio_instances: ResMut<Assets<AudioInstance>>,
 ) {
-    let music = r!(audio_instances.get_mut(&music_handle.0));
+    let music = r!(audio_instances.get_mut(&music_handle.instance));
     music.stop(AudioTween::default());
 }
 
 pub fn start_music(
     config: ConfigRef<AudioConfig>,
-    audio: Res<Audio>,
-    assets: Res<PlayingAssets>,
+    audio: Res<AudioChannel<MusicChannel>>,
+    mut audio_instances: ResMut<Assets<AudioInstance>>,
     mut music_handle: ResMut<MusicHandle>,
+    mut stems: ResMut<Stems>,
+    mut beat: ResMut<Beat>,
+    modifiers: Res<RunModifiers>,
 ) {
     let config = r!(config.get());
-    music_handle.0 = audio
-        .play(assets.music.clone())
-        .with_volume(config.music_volume)
-        .loop_from(config.music_loop_start)
-        .loop_until(config.music_loop_end)
-        .handle();
+    let track = config.track_for_level(0);
+    let music_track = r!(config.tracks.get(track));
+    beat.last_position = music_track.zeroth_beat;
+    beat.bpm = music_track.bpm * modifiers.tempo();
+    *music_handle = MusicHandle {
+        instance: r!(play_track(
+            config,
+            &audio,
+            track,
+            AudioTween::default(),
+            modifiers.tempo(),
+        )),
+        track,
+    };
+    stems.restart(
+        config,
+        &audio,
+        &mut audio_instances,
+        track,
+        AudioTween::default(),
+        modifiers.tempo(),
+    );
 }
 
-pub fn pause_music(
-    music_handle: Res<MusicHandle>,
-    mut audio_instances: ResMut<Assets<AudioInstance>>,
-) {
-    let music = r!(audio_instances.get_mut(&music_handle.0));
-    music.pause(AudioTween::default());
+/// A helper function to play a music track from its zeroth beat.
+fn play_track(
+    config: &AudioConfig,
+    audio: &AudioChannel<MusicChannel>,
+    track: usize,
+    fade_in: AudioTween,
+    playback_rate: f64,
+) -> Option<Handle<AudioInstance>> {
+    let track = config.tracks.get(track)?;
+    Some(
+        audio
+            .play(track.audio.clone())
+            .with_volume(config.music_volume * track.volume)
+            .start_from(track.zeroth_beat)
+            .loop_from(track.loop_start)
+            .loop_until(track.loop_end)
+            .fade_in(fade_in)
+            .with_playback_rate(playback_rate)
+            .handle(),
+    )
 }
 
-fn unpause_music(
-    music_handle: Res<MusicHandle>,
+/// Crossfade to the music track for the player's level, or the boss track during a boss wave.
+fn switch_music_track(
+    config: ConfigRef<AudioConfig>,
+    audio: Res<AudioChannel<MusicChannel>>,
     mut audio_instances: ResMut<Assets<AudioInstance>>,
+    mut music_handle: ResMut<MusicHandle>,
+    mut stems: ResMut<Stems>,
+    mut beat: ResMut<Beat>,
+    player_query: Query<&Level, With<IsPlayer>>,
+    boss_query: Query<(), (With<IsBoss>, Without<IsDead>)>,
+    modifiers: Res<RunModifiers>,
 ) {
-    let music = r!(audio_instances.get_mut(&music_handle.0));
-    music.resume(AudioTween::default());
+    let config = r!(config.get());
+    let level = rq!(player_query.get_single());
+    let track = config
+        .boss_track()
+        .filter(|_| !boss_query.is_empty())
+        .unwrap_or_else(|| config.track_for_level(level.current));
+    if track == music_handle.track {
+        return;
+    }
+
+    let fade = AudioTween::linear(Duration::from_secs_f64(
+        beat.beats_to_secs(8.0 * config.music_crossfade_beats),
+    ));
+    if let Some(music) = audio_instances.get_mut(&music_handle.instance) {
+        music.stop(fade.clone());
+    }
+    *music_handle = MusicHandle {
+        instance: r!(play_track(
+            config,
+            &audio,
+            track,
+            fade.clone(),
+            modifiers.tempo(),
+        )),
+        track,
+    };
+    stems.restart(
+        config,
+        &audio,
+        &mut audio_instances,
+        track,
+        fade,
+        modifiers.tempo(),
+    );
+
+    // Continue counting beats from the start of the new track.
+    beat.last_position = r!(config.tracks.get(track)).zeroth_beat;
 }
 
-#[derive(Resource, Reflect, Default)]
+#[derive(Resource, Reflect)]
 #[reflect(Resource)]
 pub struct Beat {
     /// The position of the music audio instance last frame.
@@ -80,50 +170,113 @@ pub struct Beat {
     pub total: usize,
     /// The number of new eighth-beats finished this tick (usually 0 or 1).
     pub this_tick: usize,
+    /// The progress (from 0 to 1) towards the next eighth-beat.
+    pub phase: f64,
+    /// The beats-per-minute of the music being heard.
+    pub bpm: f64,
+}
+
+impl Default for Beat {
+    fn default() -> Self {
+        Self {
+            last_position: 0.0,
+            total: 0,
+            this_tick: 0,
+            phase: 0.0,
+            // A placeholder until the music starts, so beat durations are never infinite.
+            bpm: 120.0,
+        }
+    }
+}
+
+impl Beat {
+    /// Whether a multiple of `n` eighth-beats was crossed this tick.
+    pub fn crossed(&self, n: usize) -> bool {
+        self.crossings(n) > 0
+    }
+
+    /// The number of multiples of `n` eighth-beats crossed this tick.
+    pub fn crossings(&self, n: usize) -> usize {
+        let hi = self.total;
+        let lo = hi - self.this_tick;
+        hi / n - lo / n
+    }
+
+    /// The precise number of eighth-beats counted, including the current partial eighth-beat.
+    pub fn position(&self) -> f64 {
+        self.total as f64 + self.phase
+    }
+
+    /// Convert a duration in eighth-beats to seconds at the current tempo.
+    pub fn beats_to_secs(&self, beats: f64) -> f64 {
+        beats * 60.0 / (self.bpm * 8.0)
+    }
+
+    /// Convert a duration in seconds to eighth-beats at the current tempo.
+    pub fn secs_to_beats(&self, secs: f64) -> f64 {
+        secs * self.bpm * 8.0 / 60.0
+    }
 }
 
 impl Configure for Beat {
     fn configure(app: &mut App) {
         app.register_type::<Self>();
         app.init_resource::<Self>();
-        app.add_systems(Update, update_beat.in_set(UpdateSet::SyncEarly));
+        app.add_systems(
+            Update,
+            (update_beat, mute_paused_beats.run_if(Pause::is_enabled))
+                .chain()
+                .in_set(UpdateSet::SyncEarly),
+        );
     }
 }
 
-/// A helper function to count how many beats occur in the music between two positions.
-fn count_beats(config: &AudioConfig, lo: f64, hi: f64) -> usize {
-    let lo_beats = (lo - config.music_zeroth_beat).max(0.0) * config.music_bpm * 8.0 / 60.0;
-    let hi_beats = (hi - config.music_zeroth_beat).max(0.0) * config.music_bpm * 8.0 / 60.0;
-    hi_beats as usize - lo_beats as usize
+/// A helper function to count how many beats occur in a music track between two positions.
+fn count_beats(track: &MusicTrack, lo: f64, hi: f64) -> usize {
+    track.beats_at(hi) as usize - track.beats_at(lo) as usize
 }
 
 fn update_beat(
     config: ConfigRef<AudioConfig>,
+    settings: Res<Settings>,
     music_handle: Res<MusicHandle>,
     audio_instances: ResMut<Assets<AudioInstance>>,
     mut beat: ResMut<Beat>,
+    modifiers: Res<RunModifiers>,
 ) {
     let config = r!(config.get());
-    let music = r!(audio_instances.get(&music_handle.0));
+    let track = r!(config.tracks.get(music_handle.track));
+    let music = r!(audio_instances.get(&music_handle.instance));
     let position = rq!(music.state().position());
 
+    // The position is in track time, so scale real time by the playback rate.
+    let rate = modifiers.tempo();
+    // Count beats as they're heard, rather than as they're played.
+    let latency = settings.audio_latency * rate;
     beat.this_tick = if beat.last_position <= position {
-        count_beats(config, beat.last_position, position)
+        count_beats(track, beat.last_position - latency, position - latency)
     } else {
-        count_beats(config, beat.last_position, config.music_loop_end)
-            + count_beats(config, config.music_loop_start, position)
+        count_beats(
+            track,
+            beat.last_position - latency,
+            track.loop_end - latency,
+        ) + count_beats(track, track.loop_start - latency, position - latency)
     };
     beat.total += beat.this_tick;
+    beat.phase = track.beats_at(position - latency).fract();
+    beat.bpm = track.bpm_at(position - latency) * rate;
     beat.last_position = position;
 }
 
+/// The music keeps playing while paused (ducked), so keep the beat in step with it,
+/// but don't let its beats trigger anything.
+fn mute_paused_beats(mut beat: ResMut<Beat>) {
+    beat.this_tick = 0;
+}
+
 /// A run condition to run a system every `n` eighth-beats.
 pub fn on_beat(n: usize) -> impl Fn(Res<Beat>) -> bool {
-    move |beat| {
-        let hi = beat.total;
-        let lo = hi - beat.this_tick;
-        hi / n > lo / n
-    }
+    move |beat| beat.crossed(n)
 }
 
 /// A run condition to run a system every `n` beats.
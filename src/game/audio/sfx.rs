@@ -0,0 +1,239 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use bevy_kira_audio::prelude::*;
+use pyri_state::prelude::*;
+use rand::seq::SliceRandom as _;
+use rand::Rng as _;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::core::UpdateSet;
+use crate::game::audio::mixer::SfxChannel;
+use crate::game::audio::mixer::VoiceChannel;
+use crate::game::audio::music::on_beat;
+use crate::game::audio::music::Beat;
+use crate::game::audio::music::MusicHandle;
+use crate::game::audio::AudioConfig;
+use crate::screen::Screen;
+use crate::util::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.configure::<(SfxQueue, SfxVoices)>();
+}
+
+/// How a sound effect should be played.
+#[derive(Reflect, Serialize, Deserialize, Default, Clone)]
+#[serde(deny_unknown_fields, default)]
+pub struct SfxStyle {
+    /// Delay the sound until the next eighth-beat.
+    pub quantize: bool,
+    pub pitch: SfxPitch,
+}
+
+#[derive(Reflect, Serialize, Deserialize, Default, Clone)]
+pub enum SfxPitch {
+    /// Play at the original pitch.
+    #[default]
+    Fixed,
+    /// Play at a random playback rate within a range.
+    Random(f64, f64),
+    /// Play at a random note from the music's scale, within an octave below the key.
+    Scale,
+}
+
+impl SfxPitch {
+    fn playback_rate(&self, config: &AudioConfig, key: i32) -> f64 {
+        let mut rng = rand::thread_rng();
+        match *self {
+            Self::Fixed => 1.0,
+            Self::Random(lo, hi) => rng.gen_range(lo.min(hi)..=lo.max(hi)),
+            Self::Scale => {
+                let note = config
+                    .sfx_scale
+                    .choose(&mut rng)
+                    .copied()
+                    .unwrap_or_default();
+                let octave = if rng.gen() { -12 } else { 0 };
+                2f64.powf((key + note + octave) as f64 / 12.0)
+            },
+        }
+    }
+}
+
+/// A system parameter for playing sound effects in time and in key with the music.
+#[derive(SystemParam)]
+pub struct Sfx<'w> {
+    config: ConfigRef<'w, AudioConfig>,
+    sfx_channel: Res<'w, AudioChannel<SfxChannel>>,
+    voice_channel: Res<'w, AudioChannel<VoiceChannel>>,
+    audio_instances: Res<'w, Assets<AudioInstance>>,
+    beat: Res<'w, Beat>,
+    music_handle: Res<'w, MusicHandle>,
+    queue: ResMut<'w, SfxQueue>,
+    voices: ResMut<'w, SfxVoices>,
+}
+
+impl Sfx<'_> {
+    /// Play a sound effect on the SFX channel.
+    pub fn play(&mut self, sfx: Handle<AudioSource>, volume: f64, style: &SfxStyle) {
+        self.schedule(sfx, volume, style, false);
+    }
+
+    /// Play a sound effect on the voice channel.
+    pub fn play_voice(&mut self, sfx: Handle<AudioSource>, volume: f64, style: &SfxStyle) {
+        self.schedule(sfx, volume, style, true);
+    }
+
+    fn schedule(&mut self, sfx: Handle<AudioSource>, volume: f64, style: &SfxStyle, voice: bool) {
+        let config = r!(self.config.get());
+        let key = config
+            .tracks
+            .get(self.music_handle.track)
+            .map_or(0, |track| track.key);
+        let queued = QueuedSfx {
+            beat: self.beat.total + 1,
+            sfx,
+            volume,
+            playback_rate: style.pitch.playback_rate(config, key),
+            voice,
+        };
+
+        if style.quantize {
+            self.queue.0.push(queued);
+        } else {
+            self.play_now(&queued);
+        }
+    }
+
+    /// Play a sound effect immediately, unless too many copies of it are already playing.
+    fn play_now(&mut self, queued: &QueuedSfx) {
+        let voice_cap = r!(self.config.get()).sfx_voice_cap;
+        if !self
+            .voices
+            .has_room(queued.sfx.id(), &self.audio_instances, voice_cap)
+        {
+            return;
+        }
+
+        let mut command = if queued.voice {
+            self.voice_channel.play(queued.sfx.clone())
+        } else {
+            self.sfx_channel.play(queued.sfx.clone())
+        };
+        let instance = command
+            .with_volume(queued.volume)
+            .with_playback_rate(queued.playback_rate)
+            .handle();
+        self.voices.add(queued.sfx.id(), instance);
+    }
+}
+
+#[derive(Reflect)]
+struct QueuedSfx {
+    /// The eighth-beat to play on.
+    beat: usize,
+    sfx: Handle<AudioSource>,
+    volume: f64,
+    playback_rate: f64,
+    /// Whether to play on the voice channel instead of the SFX channel.
+    voice: bool,
+}
+
+/// Sound effects waiting to be played on the beat.
+#[derive(Resource, Reflect, Default)]
+#[reflect(Resource)]
+pub struct SfxQueue(Vec<QueuedSfx>);
+
+impl Configure for SfxQueue {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.init_resource::<Self>();
+        app.add_systems(StateFlush, Screen::Playing.on_enter(clear_sfx_queue));
+        app.add_systems(
+            Update,
+            play_queued_sfx.in_set(UpdateSet::Update).run_if(on_beat(1)),
+        );
+    }
+}
+
+fn clear_sfx_queue(mut queue: ResMut<SfxQueue>) {
+    queue.0.clear();
+}
+
+fn play_queued_sfx(mut sfx: Sfx) {
+    let total = sfx.beat.total;
+    let (due, pending) = std::mem::take(&mut sfx.queue.0)
+        .into_iter()
+        .partition::<Vec<_>, _>(|queued| queued.beat <= total);
+    sfx.queue.0 = pending;
+    for queued in &due {
+        sfx.play_now(queued);
+    }
+}
+
+/// The audio instances playing for each sound effect, to cap how many can overlap.
+#[derive(Resource, Default)]
+pub struct SfxVoices(HashMap<AssetId<AudioSource>, Vec<SfxVoice>>);
+
+impl Configure for SfxVoices {
+    fn configure(app: &mut App) {
+        app.init_resource::<Self>();
+    }
+}
+
+impl SfxVoices {
+    /// Whether another copy of a sound effect can play, forgetting copies that have stopped.
+    fn has_room(
+        &mut self,
+        sfx: AssetId<AudioSource>,
+        audio_instances: &Assets<AudioInstance>,
+        voice_cap: usize,
+    ) -> bool {
+        let voices = self.0.entry(sfx).or_default();
+        voices.retain_mut(|voice| match audio_instances.get(&voice.instance) {
+            Some(instance) => {
+                voice.started = true;
+                instance.state() != PlaybackState::Stopped
+            },
+            // An instance doesn't exist until its play command is processed,
+            // so only forget it once it has existed.
+            None => !voice.started,
+        });
+        voices.len() < voice_cap
+    }
+
+    fn add(&mut self, sfx: AssetId<AudioSource>, instance: Handle<AudioInstance>) {
+        self.0.entry(sfx).or_default().push(SfxVoice {
+            instance,
+            started: false,
+        });
+    }
+}
+
+struct SfxVoice {
+    instance: Handle<AudioInstance>,
+    /// Whether the audio instance has been seen in `Assets<AudioInstance>`.
+    started: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_voice_cap_within_one_frame() {
+        let audio_instances = Assets::<AudioInstance>::default();
+        let sfx = Handle::<AudioSource>::weak_from_u128(1).id();
+        let mut voices = SfxVoices::default();
+
+        let mut played = 0;
+        for i in 0..10 {
+            if voices.has_room(sfx, &audio_instances, 3) {
+                voices.add(sfx, Handle::weak_from_u128(100 + i));
+                played += 1;
+            }
+        }
+        assert_eq!(played, 3);
+    }
+}
@@ -0,0 +1,175 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_kira_audio::prelude::*;
+use pyri_state::prelude::*;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::core::UpdateSet;
+use crate::game::actor::enemy::IsEnemy;
+use crate::game::actor::health::Health;
+use crate::game::actor::player::IsPlayer;
+use crate::game::audio::mixer::MusicChannel;
+use crate::game::audio::music::Beat;
+use crate::game::audio::music::MusicHandle;
+use crate::game::audio::AudioConfig;
+use crate::screen::playing::PlayingMenu;
+use crate::screen::Screen;
+use crate::util::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.configure::<Stems>();
+}
+
+/// The number of beats in a bar of music.
+const BAR_BEATS: usize = 4;
+
+/// A layer of a music track that's mixed in and out depending on the state of the game.
+#[derive(Reflect, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MusicStem {
+    #[serde(rename = "audio")]
+    pub audio_path: String,
+    #[serde(skip)]
+    pub audio: Handle<AudioSource>,
+    #[serde(default = "super::one")]
+    pub volume: f64,
+    #[serde(default)]
+    pub rule: StemRule,
+}
+
+/// The condition for a music stem to be audible.
+#[derive(Reflect, Serialize, Deserialize, Default, Copy, Clone)]
+pub enum StemRule {
+    #[default]
+    Always,
+    /// At least this many enemies are alive.
+    Enemies(usize),
+    /// The player's health fraction is at most this much.
+    LowHealth(f32),
+    /// The level-up menu is open.
+    LevelUpMenu,
+}
+
+/// A snapshot of the game state that the music stems respond to.
+struct Intensity {
+    enemies: usize,
+    health: f32,
+    level_up_menu: bool,
+}
+
+impl StemRule {
+    fn is_audible(self, intensity: &Intensity) -> bool {
+        match self {
+            Self::Always => true,
+            Self::Enemies(min) => intensity.enemies >= min,
+            Self::LowHealth(max) => intensity.health <= max,
+            Self::LevelUpMenu => intensity.level_up_menu,
+        }
+    }
+}
+
+/// The audio instances of the music track's stems, playing in sync with the music.
+#[derive(Resource, Reflect, Default)]
+#[reflect(Resource)]
+pub struct Stems {
+    pub instances: Vec<Handle<AudioInstance>>,
+    /// The bar of music the stems were last mixed in.
+    mixed_bar: Option<usize>,
+}
+
+impl Configure for Stems {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.init_resource::<Self>();
+        app.add_systems(StateFlush, Screen::Playing.on_exit(stop_stems));
+        // Not gated on `Pause`, so the stems still respond to the level-up menu.
+        app.add_systems(
+            Update,
+            Screen::Playing.on_update(mix_stems.in_set(UpdateSet::SyncLate)),
+        );
+    }
+}
+
+impl Stems {
+    /// Stop the current stems and start the stems of a music track in sync with it,
+    /// silent until they're mixed in.
+    pub fn restart(
+        &mut self,
+        config: &AudioConfig,
+        audio: &AudioChannel<MusicChannel>,
+        audio_instances: &mut Assets<AudioInstance>,
+        track: usize,
+        fade_out: AudioTween,
+        playback_rate: f64,
+    ) {
+        for stem in self.instances.drain(..) {
+            cq!(audio_instances.get_mut(&stem)).stop(fade_out.clone());
+        }
+        self.mixed_bar = None;
+
+        let track = r!(config.tracks.get(track));
+        self.instances = track
+            .stems
+            .iter()
+            .map(|stem| {
+                audio
+                    .play(stem.audio.clone())
+                    .with_volume(0.0)
+                    .start_from(track.zeroth_beat)
+                    .loop_from(track.loop_start)
+                    .loop_until(track.loop_end)
+                    .with_playback_rate(playback_rate)
+                    .handle()
+            })
+            .collect();
+    }
+}
+
+/// Fade each stem in or out at the start of each bar, depending on the current game intensity.
+fn mix_stems(
+    config: ConfigRef<AudioConfig>,
+    beat: Res<Beat>,
+    music_handle: Res<MusicHandle>,
+    mut stems: ResMut<Stems>,
+    mut audio_instances: ResMut<Assets<AudioInstance>>,
+    playing_menu: NextRef<PlayingMenu>,
+    enemy_query: Query<(), With<IsEnemy>>,
+    player_query: Query<&Health, With<IsPlayer>>,
+) {
+    let config = r!(config.get());
+    let track = r!(config.tracks.get(music_handle.track));
+    let music = rq!(audio_instances.get(&music_handle.instance));
+    let position = rq!(music.state().position());
+    // Volume changes apply to the audio as it's played, so this ignores the audio latency.
+    let bar = track.beats_at(position) as usize / (8 * BAR_BEATS);
+    if stems.mixed_bar == Some(bar) {
+        return;
+    }
+    stems.mixed_bar = Some(bar);
+
+    let intensity = Intensity {
+        enemies: enemy_query.iter().count(),
+        health: player_query
+            .get_single()
+            .map_or(1.0, |health| health.current / health.max),
+        level_up_menu: playing_menu.will_be_in(&PlayingMenu::LevelUp),
+    };
+
+    let fade = AudioTween::linear(Duration::from_secs_f64(beat.beats_to_secs(8.0)));
+    for (stem, instance) in track.stems.iter().zip(&stems.instances) {
+        let volume = if stem.rule.is_audible(&intensity) {
+            config.music_volume * stem.volume
+        } else {
+            0.0
+        };
+        cq!(audio_instances.get_mut(instance)).set_volume(volume, fade.clone());
+    }
+}
+
+fn stop_stems(mut stems: ResMut<Stems>, mut audio_instances: ResMut<Assets<AudioInstance>>) {
+    for stem in stems.instances.drain(..) {
+        cq!(audio_instances.get_mut(&stem)).stop(AudioTween::default());
+    }
+}
@@ -0,0 +1,221 @@
+use bevy::prelude::*;
+use pyri_state::prelude::*;
+
+use crate::core::pause::Pause;
+use crate::core::UpdateSet;
+use crate::game::actor::level::LevelConfig;
+use crate::game::actor::player::IsPlayer;
+use crate::game::audio::music::Beat;
+use crate::game::audio::AudioConfig;
+use crate::game::card::perform::PerformerMode;
+use crate::game::cleanup::DespawnOnTimer;
+use crate::screen::Screen;
+use crate::ui::prelude::*;
+use crate::util::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.configure::<(OnJudgment, Combo, IsComboDisplay, IsJudgmentPopup)>();
+
+    app.add_systems(
+        Update,
+        judge_input
+            .in_set(UpdateSet::RecordInput)
+            .run_if(Pause::is_disabled)
+            .run_if(|performer_mode: Res<PerformerMode>| !performer_mode.0),
+    );
+}
+
+/// How accurately an input was timed to the music.
+#[derive(Reflect, Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Judgment {
+    Perfect,
+    Good,
+    Miss,
+}
+
+impl Judgment {
+    /// Judge a timing error (in seconds) against a perfect and a good timing window.
+    pub fn from_error(error: f64, perfect: f64, good: f64) -> Self {
+        let error = error.abs();
+        if error <= perfect {
+            Self::Perfect
+        } else if error <= good {
+            Self::Good
+        } else {
+            Self::Miss
+        }
+    }
+
+    fn text_key(self) -> &'static str {
+        match self {
+            Self::Perfect => "judgment.perfect",
+            Self::Good => "judgment.good",
+            Self::Miss => "judgment.miss",
+        }
+    }
+
+    fn color(self) -> ThemeColor {
+        match self {
+            Self::Perfect => ThemeColor::Indicator,
+            Self::Good => ThemeColor::BodyText,
+            Self::Miss => ThemeColor::PrimaryDisabled,
+        }
+    }
+}
+
+/// An observable event triggered when an entity's input is judged.
+#[derive(Event)]
+pub struct OnJudgment(pub Judgment);
+
+impl Configure for OnJudgment {
+    fn configure(app: &mut App) {
+        app.observe(update_combo);
+        app.observe(spawn_judgment_popup);
+    }
+}
+
+/// Judge the first input pressed each eighth-beat (further presses are misses).
+fn judge_input(
+    mut commands: Commands,
+    config: ConfigRef<AudioConfig>,
+    beat: Res<Beat>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    gamepad_input: Res<ButtonInput<GamepadButton>>,
+    player_query: Query<Entity, With<IsPlayer>>,
+    mut last_judged: Local<Option<usize>>,
+) {
+    let config = r!(config.get());
+    // Menu toggles don't count as player input.
+    let pressed = keyboard_input
+        .get_just_pressed()
+        .any(|&key| key != KeyCode::Escape)
+        || mouse_input.get_just_pressed().next().is_some()
+        || gamepad_input
+            .get_just_pressed()
+            .any(|button| button.button_type != GamepadButtonType::Start);
+    if !pressed {
+        return;
+    }
+
+    let position = beat.position();
+    // Judge against the nearest eighth-beat, with the same windows as card performances.
+    let nearest = position.round().max(0.0);
+    let judgment = if *last_judged == Some(nearest as usize) {
+        Judgment::Miss
+    } else {
+        Judgment::from_error(
+            beat.beats_to_secs(position - nearest),
+            config.timing_perfect,
+            config.timing_good,
+        )
+    };
+    *last_judged = Some(nearest as usize);
+
+    for player in &player_query {
+        commands.entity(player).trigger(OnJudgment(judgment));
+    }
+}
+
+#[derive(Resource, Reflect, Default)]
+#[reflect(Resource)]
+pub struct Combo {
+    /// The number of consecutive well-timed inputs.
+    pub count: usize,
+    /// The highest combo reached this run.
+    pub best: usize,
+}
+
+impl Configure for Combo {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.init_resource::<Self>();
+        app.add_systems(StateFlush, Screen::Playing.on_enter(reset_combo));
+    }
+}
+
+impl Combo {
+    /// The XP multiplier granted by the current combo.
+    pub fn xp_multiplier(&self, config: &LevelConfig) -> f32 {
+        1.0 + (self.count as f32 * config.combo_xp_bonus).min(config.combo_xp_cap)
+    }
+}
+
+fn reset_combo(mut combo: ResMut<Combo>) {
+    *combo = default();
+}
+
+fn update_combo(trigger: Trigger<OnJudgment>, mut combo: ResMut<Combo>) {
+    if trigger.event().0 == Judgment::Miss {
+        combo.count = 0;
+    } else {
+        combo.count += 1;
+        combo.best = combo.best.max(combo.count);
+    }
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct IsComboDisplay;
+
+impl Configure for IsComboDisplay {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(
+            Update,
+            update_combo_display
+                .in_set(UpdateSet::SyncLate)
+                .run_if(resource_changed::<Combo>),
+        );
+    }
+}
+
+fn update_combo_display(combo: Res<Combo>, mut text_query: Query<&mut Text, With<IsComboDisplay>>) {
+    for mut text in &mut text_query {
+        c!(text.sections.get_mut(1)).value = combo.count.to_string();
+    }
+}
+
+/// The container for the popups that show each judgment.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct IsJudgmentPopup;
+
+impl Configure for IsJudgmentPopup {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+    }
+}
+
+fn spawn_judgment_popup(
+    trigger: Trigger<OnJudgment>,
+    mut commands: Commands,
+    mut despawn: ResMut<LateDespawn>,
+    popup_query: Query<(Entity, Option<&Children>), With<IsJudgmentPopup>>,
+) {
+    let judgment = trigger.event().0;
+
+    for (entity, children) in &popup_query {
+        // Replace the previous popup.
+        for &child in children.iter().flat_map(|children| children.iter()) {
+            despawn.recursive(child);
+        }
+
+        commands
+            .spawn((
+                Name::new("JudgmentPopup"),
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font: BOLD_FONT_HANDLE,
+                        font_size: 32.0,
+                        ..default()
+                    },
+                ),
+                LocalizedText::new(judgment.text_key()),
+                ThemeColorForText(vec![judgment.color()]),
+                DespawnOnTimer(Timer::from_seconds(0.4, TimerMode::Once)),
+            ))
+            .set_parent(entity);
+    }
+}
@@ -8,27 +8,36 @@ use iyes_progress::prelude::*;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::core::theme::ThemeConfig;
+use crate::core::UpdateSet;
 use crate::game::actor::faction::Faction;
+use crate::game::audio::sfx::Sfx;
+use crate::game::audio::sfx::SfxStyle;
 use crate::game::card::action::CardAction;
 use crate::game::card::action::CardActionKey;
 use crate::game::card::action::CardActionMap;
 use crate::game::card::action::CardActionModifier;
+use crate::game::unlock::Unlock;
 use crate::ui::prelude::*;
 use crate::util::prelude::*;
 
 pub mod action;
 pub mod attack;
+pub mod compendium;
 pub mod deck;
 pub mod movement;
+pub mod perform;
 
 pub(super) fn plugin(app: &mut App) {
-    app.configure::<(ConfigHandle<CardConfig>, OnPlayCard)>();
+    app.configure::<(ConfigHandle<CardConfig>, CardGlyph, OnPlayCard)>();
 
     app.add_plugins((
         action::plugin,
         attack::plugin,
+        compendium::plugin,
         deck::plugin,
         movement::plugin,
+        perform::plugin,
     ));
 }
 
@@ -37,6 +46,12 @@ pub struct CardConfig {
     // Deck:
     pub deck_cap: usize,
 
+    // Performance:
+    /// The power multiplier for a card performed with perfect timing.
+    pub perform_perfect_power: f32,
+    /// The power multiplier for a card performed with good timing.
+    pub perform_good_power: f32,
+
     // Cards:
     pub card_height: Val,
     pub card_background_map: HashMap<String, CardBackground>,
@@ -49,10 +64,11 @@ impl Config for CardConfig {
     const EXTENSION: &'static str = "card.ron";
 
     fn on_load(&mut self, world: &mut World) {
-        let (asset_server, card_action_map, mut layouts) = SystemState::<(
+        let (asset_server, card_action_map, mut layouts, mut icon_map) = SystemState::<(
             Res<AssetServer>,
             Res<CardActionMap>,
             ResMut<Assets<TextureAtlasLayout>>,
+            ResMut<IconMap>,
         )>::new(world)
         .get_mut(world);
 
@@ -61,8 +77,10 @@ impl Config for CardConfig {
             background.texture_atlas_layout = layouts.add(&background.texture_atlas_grid);
         }
 
-        for icon in self.card_icon_map.values_mut() {
+        for (key, icon) in &mut self.card_icon_map {
             icon.texture = asset_server.load(&icon.texture_path);
+            // Card icons can be shown inline in rich text.
+            icon_map.0.insert(key.clone(), icon.texture.clone());
         }
 
         for card in self.card_map.values_mut() {
@@ -107,6 +125,9 @@ pub struct CardBackground {
     texture_atlas_grid: TextureAtlasGrid,
     #[serde(skip)]
     pub texture_atlas_layout: Handle<TextureAtlasLayout>,
+    /// A symbol to tell this background apart without color (if the theme enables card glyphs).
+    #[serde(default)]
+    glyph: Option<String>,
     #[serde(skip)]
     active: Option<bool>,
 }
@@ -148,12 +169,72 @@ impl EntityCommand for CardBackground {
                 InteractionTable {
                     normal: atlas_off.clone(),
                     hovered: atlas_on.clone(),
-                    pressed: atlas_on,
+                    pressed: atlas_on.clone(),
                     disabled: atlas_off,
+                    focused: atlas_on,
                 },
                 InteractionSfx,
             ));
         }
+
+        if let Some(glyph) = self.glyph {
+            world.entity_mut(id).with_children(|children| {
+                children.spawn_with(card_glyph(glyph));
+            });
+        }
+    }
+}
+
+/// A symbol in the corner of a card background.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct CardGlyph;
+
+impl Configure for CardGlyph {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(Update, update_card_glyphs.in_set(UpdateSet::SyncLate));
+    }
+}
+
+fn card_glyph(glyph: String) -> impl EntityCommand<World> {
+    move |mut entity: EntityWorldMut| {
+        entity.insert((
+            Name::new("CardGlyph"),
+            TextBundle::from_section(
+                glyph,
+                TextStyle {
+                    font: BOLD_FONT_HANDLE,
+                    ..default()
+                },
+            )
+            .with_style(Style {
+                display: Display::None,
+                position_type: PositionType::Absolute,
+                top: Percent(4.0),
+                left: Percent(10.0),
+                ..default()
+            }),
+            DynamicFontSize::new(Vw(1.8)).with_step(8.0),
+            ThemeColorForText(vec![ThemeColor::CardBorder]),
+            CardGlyph,
+        ));
+    }
+}
+
+fn update_card_glyphs(
+    theme: ConfigRef<ThemeConfig>,
+    mut glyph_query: Query<&mut Style, With<CardGlyph>>,
+) {
+    let display = if r!(theme.get().and_then(ThemeConfig::active)).card_glyphs {
+        Display::Flex
+    } else {
+        Display::None
+    };
+    for mut style in &mut glyph_query {
+        if style.display != display {
+            style.display = display;
+        }
     }
 }
 
@@ -195,6 +276,15 @@ pub struct Card {
     /// The relative probability of this card being offered in the level up menu.
     #[serde(default = "one")]
     pub weight: f64,
+    /// The number of deck play slots this card occupies when played.
+    #[serde(default = "one_slot")]
+    pub beat_cost: usize,
+    /// The priority of this card when played from a hand (higher plays first).
+    #[serde(default)]
+    pub priority: i32,
+    /// The condition to unlock this card, or `None` if it's always unlocked.
+    #[serde(default)]
+    pub unlock: Option<Unlock>,
 
     #[serde(rename = "play_sfx", default)]
     play_sfx_path: String,
@@ -202,6 +292,8 @@ pub struct Card {
     pub play_sfx: Option<Handle<AudioSource>>,
     #[serde(default = "one")]
     pub play_sfx_volume: f64,
+    #[serde(default)]
+    pub play_sfx_style: SfxStyle,
     #[serde(rename = "action")]
     pub action_key: CardActionKey,
     #[serde(skip)]
@@ -209,6 +301,24 @@ pub struct Card {
     pub action_modifier: CardActionModifier,
 }
 
+impl Card {
+    /// The name in the active locale, or the name in `card.ron` if it's not translated.
+    pub fn localized_name(&self, key: &str, locale: &Locale) -> String {
+        locale
+            .get(&format!("card.{key}.name"))
+            .unwrap_or(&self.name)
+            .to_string()
+    }
+
+    /// The description in the active locale, or the description in `card.ron` if it's not translated.
+    pub fn localized_description(&self, key: &str, locale: &Locale) -> String {
+        locale
+            .get(&format!("card.{key}.description"))
+            .unwrap_or(&self.description)
+            .to_string()
+    }
+}
+
 fn inf() -> usize {
     usize::MAX
 }
@@ -217,20 +327,44 @@ fn one() -> f64 {
     1.0
 }
 
+fn one_slot() -> usize {
+    1
+}
+
 pub fn card(key: impl Into<String>, active: Option<bool>) -> impl EntityCommand {
+    card_with_height(key, active, None)
+}
+
+/// A card with a custom height, instead of `CardConfig::card_height`.
+pub fn card_with_height(
+    key: impl Into<String>,
+    active: Option<bool>,
+    height: Option<Val>,
+) -> impl EntityCommand {
     let key = key.into();
 
     move |entity: Entity, world: &mut World| {
-        let config = SystemState::<ConfigRef<CardConfig>>::new(world).get(world);
+        let (config, locale) =
+            SystemState::<(ConfigRef<CardConfig>, Locale)>::new(world).get(world);
         let config = r!(config.get());
         let card = r!(config.card_map.get(&key));
         let mut background = r!(config.card_background_map.get(&card.background_key)).clone();
         background.active = active;
         let icon = r!(config.card_icon_map.get(&card.icon_key)).clone();
         let name = format!("Card(\"{}\")", card.name);
-        let height = config.card_height;
+        let height = height.unwrap_or(config.card_height);
         let border_width = height / 18.0;
-        let tooltip_text = format!("[b]{}\n\n[r]{}", card.name, card.description);
+        let tooltip = locale.parse_rich(&format!(
+            "[b]{}\n\n[r]{}",
+            card.localized_name(&key, &locale),
+            card.localized_description(&key, &locale),
+        ));
+        let tooltip_icons = InlineIcons::from_spans(tooltip.iter().map(|(_, span)| span));
+        let tooltip_colors = TooltipColors::from_spans(tooltip.iter().map(|(_, span)| span));
+        let tooltip_text = tooltip
+            .into_iter()
+            .map(|(section, _)| section)
+            .collect::<Vec<_>>();
 
         world
             .entity_mut(entity)
@@ -246,8 +380,9 @@ pub fn card(key: impl Into<String>, active: Option<bool>) -> impl EntityCommand
                 },
                 ThemeColor::CardBorder.target::<BorderColor>(),
                 Interaction::default(),
-                Tooltip::fixed(Anchor::TopCenter, parse_rich(tooltip_text))
-                    .with_justify(JustifyText::Center),
+                Tooltip::fixed(Anchor::TopCenter, tooltip_text).with_justify(JustifyText::Center),
+                TooltipIcons(tooltip_icons),
+                tooltip_colors,
             ))
             .with_children(|children| {
                 children.spawn_with(background).with_children(|children| {
@@ -258,8 +393,9 @@ pub fn card(key: impl Into<String>, active: Option<bool>) -> impl EntityCommand
 }
 
 /// An observable event triggered when a card is played.
+/// Contains the card key and a multiplier for the power of its action.
 #[derive(Event)]
-pub struct OnPlayCard(pub String);
+pub struct OnPlayCard(pub String, pub f32);
 
 impl Configure for OnPlayCard {
     fn configure(app: &mut App) {
@@ -271,7 +407,7 @@ fn play_card(
     trigger: Trigger<OnPlayCard>,
     mut commands: Commands,
     config: ConfigRef<CardConfig>,
-    audio: Res<Audio>,
+    mut sfx: Sfx,
     faction_query: Query<&Faction>,
 ) {
     let entity = r!(trigger.get_entity());
@@ -280,8 +416,14 @@ fn play_card(
     let faction = r!(faction_query.get(entity));
 
     if let (Faction::Player, Some(play_sfx)) = (faction, card.play_sfx.clone()) {
-        audio.play(play_sfx).with_volume(card.play_sfx_volume);
+        sfx.play(play_sfx, card.play_sfx_volume, &card.play_sfx_style);
     }
 
-    commands.run_system_with_input(card.action.0, (entity, card.action_modifier.clone()));
+    commands.run_system_with_input(
+        card.action.0,
+        (
+            entity,
+            card.action_modifier.clone().with_power(trigger.event().1),
+        ),
+    );
 }
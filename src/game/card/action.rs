@@ -7,6 +7,7 @@ use serde::Serialize;
 use crate::game::actor::attack::Attack;
 use crate::game::actor::attack::AttackController;
 use crate::game::actor::health::Health;
+use crate::game::actor::health::OnHeal;
 use crate::game::actor::movement::Movement;
 use crate::game::actor::player::IsPlayer;
 use crate::game::audio::music::Beat;
@@ -94,12 +95,17 @@ impl FromWorld for CardActionMap {
                         |In((entity, modifier)): In<(Entity, CardActionModifier)>,
                          world: &mut World| {
                             let mut entity = r!(world.get_entity_mut(entity));
+                            let id = entity.id();
 
                             let mut health = r!(entity.get_mut::<Health>());
+                            let old_health = health.current;
                             health.current += modifier.heal_percent_missing / 100.0
                                 * (health.max - health.current).max(0.0);
                             health.current += modifier.heal_percent_max / 100.0 * health.max;
                             health.current += modifier.heal_flat;
+                            // Clamp now so the reported heal doesn't include overheal.
+                            health.current = health.current.min(health.max);
+                            let heal = health.current - old_health;
 
                             let mut attack_controller = r!(entity.get_mut::<AttackController>());
                             attack_controller.aim = Vec2::Y;
@@ -125,6 +131,10 @@ impl FromWorld for CardActionMap {
                                     ));
                                 }
                             }
+
+                            if heal > 0.0 {
+                                world.trigger_targets(OnHeal(heal), id);
+                            }
                         },
                     ),
                 ),
@@ -143,6 +153,19 @@ pub enum CardActionKey {
     Heal,
 }
 
+impl CardActionKey {
+    pub const ALL: [Self; 3] = [Self::Move, Self::Attack, Self::Heal];
+
+    /// The localization key for the name of this card category.
+    pub fn category_key(self) -> &'static str {
+        match self {
+            Self::Move => "category.move",
+            Self::Attack => "category.attack",
+            Self::Heal => "category.heal",
+        }
+    }
+}
+
 /// A newtyped `SystemId` with a `Default` impl.
 #[derive(Reflect, Copy, Clone)]
 #[reflect(Default)]
@@ -175,6 +198,18 @@ pub struct CardActionModifier {
     immunity: f32,
 }
 
+impl CardActionModifier {
+    /// Scale the damage and healing of this modifier by a power multiplier.
+    pub fn with_power(mut self, power: f32) -> Self {
+        self.attack.power *= power;
+        self.contact_damage *= power;
+        self.heal_percent_max *= power;
+        self.heal_percent_missing *= power;
+        self.heal_flat *= power;
+        self
+    }
+}
+
 impl Default for CardActionModifier {
     fn default() -> Self {
         Self {
@@ -0,0 +1,60 @@
+use std::ops::Deref;
+use std::ops::DerefMut;
+
+use bevy::prelude::*;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::core::UpdateSet;
+use crate::game::actor::player::IsPlayer;
+use crate::game::card::deck::Deck;
+use crate::util::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.configure::<Compendium>();
+}
+
+/// The cards the player has come across, saved between sessions.
+#[derive(Resource, Reflect, Serialize, Deserialize, Default)]
+#[reflect(Resource)]
+#[serde(default)]
+pub struct Compendium {
+    seen: SeenSet,
+}
+
+impl Configure for Compendium {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.configure::<Persistent<Self>>();
+        app.add_systems(Update, see_deck_cards.in_set(UpdateSet::SyncLate));
+    }
+}
+
+impl Persist for Compendium {
+    const KEY: &'static str = "compendium";
+}
+
+impl Deref for Compendium {
+    type Target = SeenSet;
+
+    fn deref(&self) -> &Self::Target {
+        &self.seen
+    }
+}
+
+impl DerefMut for Compendium {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.seen
+    }
+}
+
+fn see_deck_cards(
+    mut compendium: ResMut<Compendium>,
+    deck_query: Query<&Deck, (With<IsPlayer>, Changed<Deck>)>,
+) {
+    for deck in &deck_query {
+        for key in &deck.card_keys {
+            see(&mut compendium, key);
+        }
+    }
+}
@@ -1,25 +1,67 @@
+use std::cmp::Reverse;
+
 use bevy::prelude::*;
+use rand::seq::SliceRandom as _;
+use rand::Rng;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::animation::backup::Backup;
+use crate::animation::pulse::OnBeatPulse;
+use crate::animation::pulse::PulseColorFor;
+use crate::animation::pulse::PulseScale;
+use crate::core::theme::ThemeColor;
 use crate::core::UpdateSet;
 use crate::game::audio::music::on_full_beat;
+use crate::game::audio::music::Beat;
 use crate::game::card::card;
+use crate::game::card::perform::queue_card;
+use crate::game::card::perform::Performer;
+use crate::game::card::CardConfig;
 use crate::game::card::OnPlayCard;
+use crate::game::challenge::RunRng;
+use crate::game::combat::death::IsDead;
 use crate::util::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
     app.configure::<(Deck, IsDeckDisplay)>();
 }
 
-#[derive(Component, Reflect, Serialize, Deserialize, Clone, Default)]
+#[derive(Component, Reflect, Serialize, Deserialize, Clone)]
 #[reflect(Component)]
 #[serde(default)]
 pub struct Deck {
     #[serde(rename = "cards")]
     pub card_keys: Vec<String>,
+    /// The number of beats between card plays.
+    pub cadence: usize,
+    /// How the next card to play is chosen.
+    pub policy: DeckPolicy,
     #[serde(skip)]
     pub active: isize,
+    /// The indices of the cards left to draw this cycle (drawn from the back).
+    #[serde(skip)]
+    draw_pile: Vec<usize>,
+    /// The indices of the cards currently in hand.
+    #[serde(skip)]
+    hand: Vec<usize>,
+    /// The number of card plays left to skip while the active card is still playing.
+    #[serde(skip)]
+    busy: usize,
+}
+
+impl Default for Deck {
+    fn default() -> Self {
+        Self {
+            card_keys: vec![],
+            cadence: 2,
+            policy: DeckPolicy::default(),
+            active: 0,
+            draw_pile: vec![],
+            hand: vec![],
+            busy: 0,
+        }
+    }
 }
 
 impl Configure for Deck {
@@ -29,12 +71,65 @@ impl Configure for Deck {
             Update,
             play_card_from_deck
                 .in_set(UpdateSet::PlayCards)
-                .run_if(on_full_beat(2)),
+                .run_if(on_full_beat(1)),
         );
     }
 }
 
 impl Deck {
+    /// Choose the next card to play according to the deck's play policy.
+    pub fn next(&mut self, priority: impl Fn(&str) -> i32, rng: &mut impl Rng) -> Option<&String> {
+        if self.card_keys.is_empty() {
+            return None;
+        }
+
+        match self.policy {
+            DeckPolicy::RoundRobin => return self.advance(1),
+            DeckPolicy::Shuffle => {
+                if self.draw_pile.is_empty() {
+                    self.refill_draw_pile();
+                    self.draw_pile.shuffle(rng);
+                }
+                self.active = self.draw_pile.pop()? as isize;
+            },
+            DeckPolicy::Hand(size) => {
+                let size = size.clamp(1, self.card_keys.len());
+                while self.hand.len() < size {
+                    if self.draw_pile.is_empty() {
+                        self.refill_draw_pile();
+                    }
+                    let idx = self.draw_pile.pop()?;
+                    self.hand.push(idx);
+                }
+
+                // Play the highest priority card, breaking ties by draw order.
+                let (i, _) = self
+                    .hand
+                    .iter()
+                    .enumerate()
+                    .max_by_key(|&(i, &idx)| (priority(&self.card_keys[idx]), Reverse(i)))?;
+                self.active = self.hand.remove(i) as isize;
+            },
+        }
+
+        Some(&self.card_keys[self.active as usize])
+    }
+
+    /// Refill the draw pile with every card that isn't already in hand.
+    fn refill_draw_pile(&mut self) {
+        let hand = &self.hand;
+        self.draw_pile = (0..self.card_keys.len())
+            .rev()
+            .filter(|idx| !hand.contains(idx))
+            .collect();
+    }
+
+    /// Forget the current draw pile and hand (e.g. after the cards have been edited).
+    fn reset_cycle(&mut self) {
+        self.draw_pile.clear();
+        self.hand.clear();
+    }
+
     pub fn advance(&mut self, step: isize) -> Option<&String> {
         if self.card_keys.is_empty() {
             return None;
@@ -56,6 +151,51 @@ impl Deck {
         if old < self.card_keys.len() {
             self.card_keys.swap(old, self.active as usize);
         }
+        self.reset_cycle();
+    }
+
+    /// The number of card plays until the card at `idx` is played, if it's known yet.
+    pub fn plays_until(&self, idx: usize) -> Option<usize> {
+        let len = self.card_keys.len();
+        if idx >= len {
+            return None;
+        }
+
+        match self.policy {
+            DeckPolicy::RoundRobin => {
+                let plays = (idx as isize - self.active.max(0)).rem_euclid(len as isize) as usize;
+                Some(if plays == 0 { len } else { plays })
+            },
+            // Cards are drawn from the back of the draw pile.
+            DeckPolicy::Shuffle => self
+                .draw_pile
+                .iter()
+                .rev()
+                .position(|&x| x == idx)
+                .map(|i| i + 1),
+            // The next card depends on the cards that haven't been drawn yet.
+            DeckPolicy::Hand(_) => None,
+        }
+    }
+
+    /// The number of card plays left to skip while the active card is still playing.
+    pub fn busy(&self) -> usize {
+        self.busy
+    }
+
+    /// Keep the active card playing for `beat_cost` card plays in total.
+    pub fn occupy(&mut self, beat_cost: usize) {
+        self.busy = beat_cost.saturating_sub(1);
+    }
+
+    /// Skip a card play if the active card is still playing, and return whether it was skipped.
+    fn skip_if_busy(&mut self) -> bool {
+        if self.busy == 0 {
+            return false;
+        }
+
+        self.busy -= 1;
+        true
     }
 
     pub fn discard(&mut self) {
@@ -68,21 +208,64 @@ impl Deck {
         if idx >= self.card_keys.len() {
             self.active = 0;
         }
+        self.reset_cycle();
     }
 
     pub fn add(&mut self, card_key: impl Into<String>) {
         let idx = self.active.max(0) as usize;
         self.card_keys.insert(idx, card_key.into());
+        self.reset_cycle();
     }
 }
 
-fn play_card_from_deck(mut commands: Commands, mut deck_query: Query<(Entity, &mut Deck)>) {
-    for (entity, mut deck) in &mut deck_query {
-        let card_key = c!(deck.advance(1));
+/// How a deck chooses its next card to play.
+#[derive(Reflect, Serialize, Deserialize, Copy, Clone, Default)]
+pub enum DeckPolicy {
+    /// Play each card in order.
+    #[default]
+    RoundRobin,
+    /// Play each card once per cycle, in a random order.
+    Shuffle,
+    /// Draw a hand of N cards in order and play the card with the highest priority.
+    Hand(usize),
+}
 
-        commands
-            .entity(entity)
-            .trigger(OnPlayCard(card_key.clone()));
+fn play_card_from_deck(
+    mut commands: Commands,
+    config: ConfigRef<CardConfig>,
+    beat: Res<Beat>,
+    mut rng: ResMut<RunRng>,
+    mut deck_query: Query<(Entity, &mut Deck, Has<Performer>), Without<IsDead>>,
+) {
+    let config = r!(config.get());
+    let full_beat = beat.total / 8;
+
+    for (entity, mut deck, is_performer) in &mut deck_query {
+        if full_beat % deck.cadence.max(1) != 0 {
+            continue;
+        }
+        if deck.skip_if_busy() {
+            continue;
+        }
+
+        let card_key = c!(deck.next(
+            |key| config.card_map.get(key).map_or(0, |card| card.priority),
+            &mut rng.0,
+        ))
+        .clone();
+        let beat_cost = config
+            .card_map
+            .get(&card_key)
+            .map_or(1, |card| card.beat_cost);
+        deck.occupy(beat_cost);
+
+        if is_performer {
+            commands
+                .entity(entity)
+                .add(queue_card(card_key, beat.total));
+        } else {
+            commands.entity(entity).trigger(OnPlayCard(card_key, 1.0));
+        }
     }
 }
 
@@ -135,8 +318,127 @@ fn populate_deck_display(
 
         commands.entity(entity).with_children(|children| {
             for (i, card_key) in deck.card_keys.iter().enumerate() {
-                children.spawn_with(card(card_key, Some(i as isize == deck.active)));
+                let active = i as isize == deck.active;
+                let mut card = children.spawn_with(card(card_key, Some(active)));
+                if active {
+                    card.insert((
+                        PulseScale(OnBeatPulse::new(8), 0.08),
+                        PulseColorFor::<BorderColor>::new(
+                            OnBeatPulse::new(8),
+                            ThemeColor::Indicator,
+                            0.5,
+                        ),
+                        Backup::<Transform>::default(),
+                    ));
+                }
             }
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng as _;
+
+    use super::*;
+
+    fn deck(policy: DeckPolicy, cards: &[&str]) -> Deck {
+        Deck {
+            card_keys: cards.iter().map(|x| x.to_string()).collect(),
+            policy,
+            ..default()
+        }
+    }
+
+    fn play(deck: &mut Deck, n: usize, priority: impl Fn(&str) -> i32) -> Vec<String> {
+        let mut rng = SmallRng::seed_from_u64(0);
+        (0..n)
+            .filter_map(|_| deck.next(&priority, &mut rng).cloned())
+            .collect()
+    }
+
+    #[test]
+    fn test_empty_deck() {
+        for policy in [
+            DeckPolicy::RoundRobin,
+            DeckPolicy::Shuffle,
+            DeckPolicy::Hand(2),
+        ] {
+            assert!(play(&mut deck(policy, &[]), 3, |_| 0).is_empty());
+        }
+    }
+
+    #[test]
+    fn test_round_robin() {
+        let mut deck = deck(DeckPolicy::RoundRobin, &["a", "b", "c"]);
+        assert_eq!(deck.plays_until(1), Some(1));
+        assert_eq!(deck.plays_until(0), Some(3));
+        assert_eq!(deck.plays_until(3), None);
+        assert_eq!(play(&mut deck, 4, |_| 0), ["b", "c", "a", "b"]);
+    }
+
+    #[test]
+    fn test_shuffle_plays_each_card_once_per_cycle() {
+        let mut deck = deck(DeckPolicy::Shuffle, &["a", "b", "c", "d"]);
+        for _ in 0..3 {
+            let mut cycle = play(&mut deck, 4, |_| 0);
+            cycle.sort();
+            assert_eq!(cycle, ["a", "b", "c", "d"]);
+        }
+    }
+
+    #[test]
+    fn test_shuffle_plays_until() {
+        let mut deck = deck(DeckPolicy::Shuffle, &["a", "b", "c", "d"]);
+        play(&mut deck, 1, |_| 0);
+        let upcoming = deck.draw_pile.iter().rev().copied().collect::<Vec<_>>();
+        for (i, &idx) in upcoming.iter().enumerate() {
+            assert_eq!(deck.plays_until(idx), Some(i + 1));
+        }
+        assert_eq!(deck.plays_until(deck.active as usize), None);
+    }
+
+    #[test]
+    fn test_hand_plays_highest_priority() {
+        let priority = |key: &str| if key == "heal" { 1 } else { 0 };
+        let mut deck = deck(DeckPolicy::Hand(2), &["a", "heal", "b", "c"]);
+        // Hand: [a, heal] -> heal. Hand: [a, b] -> a (tie, drawn first). Hand: [b, c] -> b.
+        assert_eq!(play(&mut deck, 3, priority), ["heal", "a", "b"]);
+        assert_eq!(deck.plays_until(0), None);
+    }
+
+    #[test]
+    fn test_hand_size_is_clamped() {
+        let mut big_hand = deck(DeckPolicy::Hand(10), &["a", "b"]);
+        assert_eq!(play(&mut big_hand, 4, |_| 0).len(), 4);
+        let mut empty_hand = deck(DeckPolicy::Hand(0), &["a", "b"]);
+        assert_eq!(play(&mut empty_hand, 2, |_| 0), ["a", "b"]);
+    }
+
+    #[test]
+    fn test_beat_cost_skips_plays() {
+        let mut deck = deck(DeckPolicy::RoundRobin, &["a", "b"]);
+        deck.occupy(3);
+        assert_eq!(deck.busy(), 2);
+        assert!(deck.skip_if_busy());
+        assert!(deck.skip_if_busy());
+        assert!(!deck.skip_if_busy());
+
+        deck.occupy(1);
+        assert!(!deck.skip_if_busy());
+        deck.occupy(0);
+        assert!(!deck.skip_if_busy());
+    }
+
+    #[test]
+    fn test_editing_resets_cycle() {
+        let mut deck = deck(DeckPolicy::Shuffle, &["a", "b", "c"]);
+        play(&mut deck, 1, |_| 0);
+        deck.add("d");
+        assert!(deck.draw_pile.is_empty());
+        let mut cycle = play(&mut deck, 4, |_| 0);
+        cycle.sort();
+        assert_eq!(cycle, ["a", "b", "c", "d"]);
+    }
+}
@@ -0,0 +1,181 @@
+use bevy::ecs::system::EntityCommand;
+use bevy::prelude::*;
+use leafwing_input_manager::prelude::*;
+use pyri_state::prelude::*;
+
+use crate::core::pause::Pause;
+use crate::core::UpdateSet;
+use crate::game::audio::music::Beat;
+use crate::game::audio::timing::Judgment;
+use crate::game::audio::timing::OnJudgment;
+use crate::game::audio::AudioConfig;
+use crate::game::card::CardConfig;
+use crate::game::card::OnPlayCard;
+use crate::util::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.configure::<(PerformerMode, PerformAction, Performer)>();
+}
+
+/// Whether the player performs their cards manually, in time with the music.
+#[derive(Resource, Reflect, Default)]
+#[reflect(Resource)]
+pub struct PerformerMode(pub bool);
+
+impl Configure for PerformerMode {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.init_resource::<Self>();
+    }
+}
+
+#[derive(Eq, PartialEq, Hash, Copy, Clone, Reflect, Debug)]
+enum PerformAction {
+    Play,
+}
+
+impl Actionlike for PerformAction {
+    fn input_control_kind(&self) -> InputControlKind {
+        match self {
+            Self::Play => InputControlKind::Button,
+        }
+    }
+}
+
+impl Configure for PerformAction {
+    fn configure(app: &mut App) {
+        app.add_plugins(InputManagerPlugin::<Self>::default());
+    }
+}
+
+pub fn perform_action(mut entity: EntityWorldMut) {
+    entity.insert(InputManagerBundle::with_map(
+        InputMap::default()
+            .with(PerformAction::Play, KeyCode::Space)
+            .with(PerformAction::Play, GamepadButtonType::South),
+    ));
+}
+
+/// Holds cards played from the deck until they're performed on the beat.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct Performer {
+    /// The card waiting to be performed, and the eighth-beat it's due on.
+    pending: Option<(String, usize)>,
+    /// The beat position of the latest press.
+    last_press: Option<f64>,
+}
+
+impl Configure for Performer {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(
+            Update,
+            perform_cards
+                .in_set(UpdateSet::RecordInput)
+                .run_if(Pause::is_disabled),
+        );
+    }
+}
+
+/// Queue a card to be performed on the given eighth-beat.
+/// Any card still waiting to be performed is missed.
+pub fn queue_card(card_key: impl Into<String>, due: usize) -> impl EntityCommand {
+    let card_key = card_key.into();
+    move |entity: Entity, world: &mut World| {
+        let mut performer = r!(world.get_mut::<Performer>(entity));
+        if performer.pending.replace((card_key, due)).is_some() {
+            world.trigger_targets(OnJudgment(Judgment::Miss), entity);
+        }
+    }
+}
+
+fn perform_cards(
+    mut commands: Commands,
+    audio_config: ConfigRef<AudioConfig>,
+    card_config: ConfigRef<CardConfig>,
+    beat: Res<Beat>,
+    mut performer_query: Query<(Entity, &ActionState<PerformAction>, &mut Performer)>,
+) {
+    let audio_config = r!(audio_config.get());
+    let card_config = r!(card_config.get());
+    let now = beat.position();
+
+    for (entity, action, mut performer) in &mut performer_query {
+        if action.just_pressed(&PerformAction::Play) {
+            performer.last_press = Some(now);
+        }
+        let Some((_, due)) = performer.pending else {
+            // Forget presses that are too old to count as an early press for the next card.
+            if performer
+                .last_press
+                .is_some_and(|press| beat.beats_to_secs(now - press) > audio_config.timing_good)
+            {
+                performer.last_press = None;
+            }
+            continue;
+        };
+        let due = due as f64;
+
+        // Judge the latest press, or wait until it's too late to press.
+        let judgment = performer
+            .last_press
+            .map(|press| {
+                Judgment::from_error(
+                    beat.beats_to_secs(press - due),
+                    audio_config.timing_perfect,
+                    audio_config.timing_good,
+                )
+            })
+            .filter(|&judgment| judgment != Judgment::Miss);
+        let judgment = match judgment {
+            Some(judgment) => judgment,
+            None if beat.beats_to_secs(now - due) > audio_config.timing_good => Judgment::Miss,
+            None => continue,
+        };
+
+        let (card_key, _) = c!(performer.pending.take());
+        performer.last_press = None;
+        commands.entity(entity).trigger(OnJudgment(judgment));
+        let power = match judgment {
+            Judgment::Perfect => card_config.perform_perfect_power,
+            Judgment::Good => card_config.perform_good_power,
+            // Missed cards are skipped.
+            Judgment::Miss => continue,
+        };
+
+        commands.entity(entity).trigger(OnPlayCard(card_key, power));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Resource, Default)]
+    struct Misses(usize);
+
+    #[test]
+    fn test_replaced_card_is_missed() {
+        let mut world = World::new();
+        world.init_resource::<Misses>();
+        world.observe(|trigger: Trigger<OnJudgment>, mut misses: ResMut<Misses>| {
+            if trigger.event().0 == Judgment::Miss {
+                misses.0 += 1;
+            }
+        });
+        let entity = world.spawn(Performer::default()).id();
+
+        queue_card("step", 8).apply(entity, &mut world);
+        world.flush();
+        assert_eq!(world.resource::<Misses>().0, 0);
+
+        queue_card("pair", 16).apply(entity, &mut world);
+        world.flush();
+        assert_eq!(world.resource::<Misses>().0, 1);
+        assert_eq!(
+            world.get::<Performer>(entity).unwrap().pending,
+            Some(("pair".to_string(), 16)),
+        );
+    }
+}
@@ -0,0 +1,362 @@
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use chrono::NaiveDate;
+use pyri_state::prelude::*;
+use rand::rngs::SmallRng;
+use rand::seq::SliceRandom as _;
+use rand::SeedableRng as _;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::core::UpdateSet;
+use crate::game::actor::enemy::IsEnemy;
+use crate::game::actor::health::Health;
+use crate::game::audio::music::on_full_beat;
+use crate::game::card::action::CardActionKey;
+use crate::game::card::deck::Deck;
+use crate::game::card::Card;
+use crate::game::combat::death::IsDead;
+use crate::game::score::award_victory_bonus;
+use crate::game::score::Score;
+use crate::screen::playing::PlayingMenu;
+use crate::screen::Screen;
+use crate::ui::prelude::*;
+use crate::util::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.configure::<(
+        ConfigHandle<ChallengeConfig>,
+        ActiveChallenge,
+        RunModifiers,
+        RunRng,
+        ChallengeRecords,
+    )>();
+}
+
+#[derive(Asset, Reflect, Serialize, Deserialize)]
+pub struct ChallengeConfig {
+    pub modifiers: HashMap<String, RunModifier>,
+    /// The number of modifiers in a daily challenge.
+    pub daily_modifiers: usize,
+    /// The number of modifiers in a weekly challenge.
+    pub weekly_modifiers: usize,
+}
+
+impl Config for ChallengeConfig {
+    const PATH: &'static str = "config/challenge.ron";
+    const EXTENSION: &'static str = "challenge.ron";
+}
+
+#[derive(Reflect, Serialize, Deserialize, Clone)]
+pub struct RunModifier {
+    pub name: String,
+    pub description: String,
+    pub effect: ModifierEffect,
+}
+
+impl RunModifier {
+    /// The name in the active locale, or the name in `challenge.ron` if it's not translated.
+    pub fn localized_name(&self, key: &str, locale: &Locale) -> String {
+        locale
+            .get(&format!("modifier.{key}.name"))
+            .unwrap_or(&self.name)
+            .to_string()
+    }
+
+    /// The description in the active locale, or the description in `challenge.ron` if it's not translated.
+    pub fn localized_description(&self, key: &str, locale: &Locale) -> String {
+        locale
+            .get(&format!("modifier.{key}.description"))
+            .unwrap_or(&self.description)
+            .to_string()
+    }
+}
+
+#[derive(Reflect, Serialize, Deserialize, Clone)]
+pub enum ModifierEffect {
+    /// Multiply the music tempo.
+    Tempo(f64),
+    /// Add a card to the player's starting deck.
+    StartWithCard(String),
+    /// Heal every enemy by this much on every beat.
+    EnemyHealPerBeat(f32),
+    /// Never offer cards of this category in the level up menu.
+    NoOffers(CardActionKey),
+    /// Multiply the number of enemies spawned per wave.
+    SpawnScale(f32),
+}
+
+#[derive(Reflect, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum ChallengeKind {
+    Daily,
+    Weekly,
+}
+
+/// A challenge run with a fixed seed and modifiers for the current day or week.
+#[derive(Reflect, Clone)]
+pub struct Challenge {
+    pub kind: ChallengeKind,
+    /// The number of days (or weeks) since the Unix epoch.
+    pub period: u64,
+    pub seed: u64,
+    /// The keys of the modifiers in `ChallengeConfig::modifiers`.
+    pub modifiers: Vec<String>,
+}
+
+impl Challenge {
+    /// The current challenge of a kind, based on today's local date.
+    pub fn current(kind: ChallengeKind, config: &ChallengeConfig) -> Self {
+        let days = clock::days_since_epoch();
+        let (period, count) = match kind {
+            ChallengeKind::Daily => (days, config.daily_modifiers),
+            // Weeks start on Monday (and the Unix epoch was a Thursday).
+            ChallengeKind::Weekly => ((days + 3) / 7, config.weekly_modifiers),
+        };
+        let seed = split_mix(period.wrapping_mul(2) + (kind == ChallengeKind::Weekly) as u64);
+
+        // Sort the keys so the choice only depends on the seed.
+        let mut keys = config.modifiers.keys().cloned().collect::<Vec<_>>();
+        keys.sort();
+        let modifiers = keys
+            .choose_multiple(&mut SmallRng::seed_from_u64(seed), count)
+            .cloned()
+            .collect();
+
+        Self {
+            kind,
+            period,
+            seed,
+            modifiers,
+        }
+    }
+
+    /// The key to save this challenge's records under.
+    pub fn id(&self) -> String {
+        match self.kind {
+            ChallengeKind::Daily => format!("daily-{}", self.period),
+            ChallengeKind::Weekly => format!("weekly-{}", self.period),
+        }
+    }
+
+    /// The first day of this challenge.
+    pub fn start_date(&self) -> Option<NaiveDate> {
+        let days = match self.kind {
+            ChallengeKind::Daily => self.period,
+            ChallengeKind::Weekly => (self.period * 7).saturating_sub(3),
+        };
+        clock::date_from_days(days)
+    }
+}
+
+/// Scramble a number into a well-distributed seed.
+fn split_mix(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9e3779b97f4a7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+/// The challenge being played, if any.
+#[derive(Resource, Reflect, Default)]
+#[reflect(Resource)]
+pub struct ActiveChallenge(pub Option<Challenge>);
+
+impl Configure for ActiveChallenge {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.init_resource::<Self>();
+        app.add_systems(
+            StateFlush,
+            (
+                Screen::Title.on_enter(clear_active_challenge),
+                PlayingMenu::Victory.on_enter(record_challenge_score.after(award_victory_bonus)),
+                PlayingMenu::Defeat.on_enter(record_challenge_score),
+            ),
+        );
+    }
+}
+
+fn clear_active_challenge(mut challenge: ResMut<ActiveChallenge>) {
+    challenge.0 = None;
+}
+
+/// The modifiers in effect for the current run.
+/// Systems that a modifier can affect should go through these hooks instead of reading their config directly.
+#[derive(Resource, Reflect, Default)]
+#[reflect(Resource)]
+pub struct RunModifiers(pub Vec<ModifierEffect>);
+
+impl Configure for RunModifiers {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.init_resource::<Self>();
+        app.add_systems(
+            Update,
+            Screen::Playing.on_update(
+                heal_enemies_on_beat
+                    .in_set(UpdateSet::Update)
+                    .run_if(on_full_beat(1)),
+            ),
+        );
+    }
+}
+
+impl RunModifiers {
+    /// The music playback rate.
+    pub fn tempo(&self) -> f64 {
+        self.0
+            .iter()
+            .filter_map(|x| match x {
+                ModifierEffect::Tempo(rate) => Some(rate),
+                _ => None,
+            })
+            .product()
+    }
+
+    /// Add any extra starting cards to a deck.
+    pub fn modify_deck(&self, deck: &mut Deck) {
+        for effect in &self.0 {
+            if let ModifierEffect::StartWithCard(key) = effect {
+                deck.card_keys.push(key.clone());
+            }
+        }
+    }
+
+    /// Whether a card can be offered in the level up menu.
+    pub fn allows_offer(&self, card: &Card) -> bool {
+        !self
+            .0
+            .iter()
+            .any(|x| matches!(x, ModifierEffect::NoOffers(key) if *key == card.action_key))
+    }
+
+    /// The number of enemies to spawn in a wave.
+    pub fn spawn_count(&self, count: usize) -> usize {
+        let scale = self
+            .0
+            .iter()
+            .filter_map(|x| match x {
+                ModifierEffect::SpawnScale(scale) => Some(scale),
+                _ => None,
+            })
+            .product::<f32>();
+        (count as f32 * scale).round() as usize
+    }
+
+    /// The amount to heal every enemy by on every beat.
+    pub fn enemy_heal_per_beat(&self) -> f32 {
+        self.0
+            .iter()
+            .filter_map(|x| match x {
+                ModifierEffect::EnemyHealPerBeat(heal) => Some(heal),
+                _ => None,
+            })
+            .sum()
+    }
+}
+
+fn heal_enemies_on_beat(
+    modifiers: Res<RunModifiers>,
+    mut health_query: Query<&mut Health, (With<IsEnemy>, Without<IsDead>)>,
+) {
+    let heal = modifiers.enemy_heal_per_beat();
+    if heal == 0.0 {
+        return;
+    }
+
+    for mut health in &mut health_query {
+        if health.current < health.max {
+            health.current = (health.current + heal).min(health.max);
+        }
+    }
+}
+
+/// The source of randomness for gameplay, seeded at the start of each run.
+#[derive(Resource, Deref, DerefMut)]
+pub struct RunRng(pub SmallRng);
+
+impl Configure for RunRng {
+    fn configure(app: &mut App) {
+        app.insert_resource(Self(SmallRng::from_entropy()));
+    }
+}
+
+/// Set up the modifiers and RNG for a new run, before anything is spawned.
+pub fn prepare_run(
+    config: ConfigRef<ChallengeConfig>,
+    challenge: Res<ActiveChallenge>,
+    mut modifiers: ResMut<RunModifiers>,
+    mut rng: ResMut<RunRng>,
+) {
+    let Some(challenge) = &challenge.0 else {
+        modifiers.0.clear();
+        rng.0 = SmallRng::from_entropy();
+        return;
+    };
+
+    let config = r!(config.get());
+    modifiers.0 = challenge
+        .modifiers
+        .iter()
+        .filter_map(|key| config.modifiers.get(key))
+        .map(|modifier| modifier.effect.clone())
+        .collect();
+    rng.0 = SmallRng::seed_from_u64(challenge.seed);
+}
+
+/// The best score for each challenge, saved between sessions.
+#[derive(Resource, Reflect, Serialize, Deserialize, Default)]
+#[reflect(Resource)]
+#[serde(default)]
+pub struct ChallengeRecords {
+    /// The best score by challenge ID.
+    pub best: HashMap<String, u64>,
+}
+
+impl Configure for ChallengeRecords {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.configure::<Persistent<Self>>();
+    }
+}
+
+impl Persist for ChallengeRecords {
+    const KEY: &'static str = "challenge_records";
+}
+
+fn record_challenge_score(
+    challenge: Res<ActiveChallenge>,
+    score: Res<Score>,
+    mut records: ResMut<ChallengeRecords>,
+) {
+    let challenge = rq!(challenge.0.as_ref());
+    let score = score.total();
+    let id = challenge.id();
+    if records.best.get(&id).is_some_and(|&best| best >= score) {
+        return;
+    }
+
+    records.best.insert(id, score);
+}
+
+mod clock {
+    use chrono::Datelike as _;
+    use chrono::NaiveDate;
+
+    /// The number of days from 0001-01-01 to the Unix epoch.
+    const UNIX_EPOCH_DAYS_FROM_CE: i32 = 719_163;
+
+    /// The number of whole days since the Unix epoch, on the local date.
+    pub fn days_since_epoch() -> u64 {
+        let days = chrono::Local::now().date_naive().num_days_from_ce();
+        (days - UNIX_EPOCH_DAYS_FROM_CE).max(0) as u64
+    }
+
+    /// The date a number of days after the Unix epoch.
+    pub fn date_from_days(days: u64) -> Option<NaiveDate> {
+        let days = i32::try_from(days)
+            .ok()?
+            .checked_add(UNIX_EPOCH_DAYS_FROM_CE)?;
+        NaiveDate::from_num_days_from_ce_opt(days)
+    }
+}
@@ -2,6 +2,7 @@ pub mod damage;
 pub mod death;
 pub mod hit;
 pub mod knockback;
+pub mod number;
 pub mod projectile;
 
 use bevy::prelude::*;
@@ -12,6 +13,7 @@ pub(super) fn plugin(app: &mut App) {
         death::plugin,
         hit::plugin,
         knockback::plugin,
+        number::plugin,
         projectile::plugin,
     ));
 }
@@ -1,11 +1,20 @@
+use avian2d::prelude::*;
 use bevy::prelude::*;
 use bevy_kira_audio::prelude::*;
-use rand::Rng as _;
+use serde::Deserialize;
+use serde::Serialize;
 
+use crate::core::UpdateSet;
+use crate::game::audio::music::on_beat;
+use crate::game::audio::sfx::Sfx;
+use crate::game::audio::AudioConfig;
+use crate::game::combat::hit::Hurtbox;
+use crate::game::sprite::SpriteAnimationFrame;
+use crate::game::vfx::ParticleBurst;
 use crate::util::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
-    app.configure::<(IsDead, DespawnOnDeath, DeathSfx)>();
+    app.configure::<(IsDead, DespawnOnDeath, DeathAnimation, DeathSfx)>();
 }
 
 /// An observable event triggered when an actor dies.
@@ -42,7 +51,7 @@ impl Configure for DespawnOnDeath {
 
 fn despawn_on_death(
     trigger: Trigger<OnDeath>,
-    despawn_query: Query<(), With<DespawnOnDeath>>,
+    despawn_query: Query<(), (With<DespawnOnDeath>, Without<DeathAnimation>)>,
     mut despawn: ResMut<LateDespawn>,
 ) {
     let entity = r!(trigger.get_entity());
@@ -51,6 +60,106 @@ fn despawn_on_death(
     }
 }
 
+/// A death animation that plays once when an actor dies.
+/// With `DespawnOnDeath`, the actor will be despawned when the animation is finished.
+#[derive(Component, Reflect, Serialize, Deserialize, Clone, Default)]
+#[reflect(Component)]
+#[serde(default)]
+pub struct DeathAnimation {
+    /// The sprite frames to play (the current frame is held if empty).
+    pub frames: Vec<SpriteAnimationFrame>,
+    /// The particle emitter to burst into confetti with.
+    pub confetti: Option<String>,
+    /// The time scale to play the game at while the animation plays.
+    pub slow_motion: Option<SlowMotion>,
+    /// The number of eighth-beats since death.
+    #[serde(skip)]
+    beats: usize,
+}
+
+impl Configure for DeathAnimation {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.observe(start_death_animation);
+        app.add_systems(
+            Update,
+            update_death_animation
+                .in_set(UpdateSet::SyncLate)
+                .run_if(on_beat(1)),
+        );
+    }
+}
+
+impl DeathAnimation {
+    fn total_beats(&self) -> usize {
+        self.frames.iter().map(|x| x.beats).sum()
+    }
+
+    /// Calculate the texture atlas index of the animation after `beats` beats.
+    fn index(&self, beats: usize) -> Option<usize> {
+        let mut beats = beats;
+        for frame in &self.frames {
+            if beats < frame.beats {
+                return Some(frame.index);
+            }
+            beats -= frame.beats;
+        }
+
+        self.frames.last().map(|x| x.index)
+    }
+}
+
+/// Slow down game time for a dramatic moment.
+#[derive(Reflect, Serialize, Deserialize, Copy, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct SlowMotion {
+    /// The relative speed of game time.
+    pub speed: f32,
+    /// The duration in real time (in seconds).
+    pub secs: f32,
+}
+
+fn start_death_animation(
+    trigger: Trigger<OnDeath>,
+    mut commands: Commands,
+    mut burst_events: EventWriter<ParticleBurst>,
+    mut anim_query: Query<(&mut DeathAnimation, &GlobalTransform)>,
+) {
+    let entity = r!(trigger.get_entity());
+    let (mut anim, gt) = rq!(anim_query.get_mut(entity));
+    anim.beats = 0;
+
+    // Stop taking hits or pushing other actors around.
+    commands.entity(entity).remove::<Hurtbox>().insert(Sensor);
+
+    if let Some(confetti) = &anim.confetti {
+        burst_events.send(ParticleBurst::new(confetti, gt.translation()));
+    }
+}
+
+fn update_death_animation(
+    mut despawn: ResMut<LateDespawn>,
+    mut anim_query: Query<
+        (
+            Entity,
+            &mut DeathAnimation,
+            &mut TextureAtlas,
+            Has<DespawnOnDeath>,
+        ),
+        With<IsDead>,
+    >,
+) {
+    for (entity, mut anim, mut atlas, despawn_on_death) in &mut anim_query {
+        if let Some(index) = anim.index(anim.beats) {
+            atlas.index = index;
+        }
+        if anim.beats >= anim.total_beats() && despawn_on_death {
+            despawn.recursive(entity);
+        }
+        anim.beats += 1;
+    }
+}
+
 #[derive(Component, Reflect)]
 #[reflect(Component)]
 pub struct DeathSfx(pub Handle<AudioSource>, pub f64);
@@ -62,11 +171,14 @@ impl Configure for DeathSfx {
     }
 }
 
-fn play_death_sfx(trigger: Trigger<OnDeath>, sfx_query: Query<&DeathSfx>, audio: Res<Audio>) {
+fn play_death_sfx(
+    trigger: Trigger<OnDeath>,
+    config: ConfigRef<AudioConfig>,
+    sfx_query: Query<&DeathSfx>,
+    mut sfx: Sfx,
+) {
+    let config = r!(config.get());
     let entity = r!(trigger.get_entity());
-    let sfx = r!(sfx_query.get(entity));
-    audio
-        .play(sfx.0.clone())
-        .with_volume(sfx.1)
-        .with_playback_rate(rand::thread_rng().gen_range(0.8..1.4));
+    let death_sfx = r!(sfx_query.get(entity));
+    sfx.play_voice(death_sfx.0.clone(), death_sfx.1, &config.death_sfx);
 }
@@ -1,9 +1,10 @@
 use avian2d::prelude::*;
 use bevy::prelude::*;
 use bevy_kira_audio::prelude::*;
-use rand::Rng as _;
 
 use crate::core::UpdateSet;
+use crate::game::audio::sfx::Sfx;
+use crate::game::audio::AudioConfig;
 use crate::game::cleanup::RemoveOnTimer;
 use crate::util::prelude::*;
 
@@ -56,17 +57,27 @@ fn trigger_hit(
     mut commands: Commands,
     mut collision_events: EventReader<CollisionStarted>,
     hitbox_query: Query<(), With<Hitbox>>,
-    hurtbox_query: Query<(), (With<Hurtbox>, Without<Immune>)>,
+    hurtbox_query: Query<Has<Immune>, With<Hurtbox>>,
 ) {
     for &CollisionStarted(a, b) in collision_events.read() {
         for (a, b) in [(a, b), (b, a)] {
-            if hitbox_query.contains(a) && hurtbox_query.contains(b) {
+            if !hitbox_query.contains(a) {
+                continue;
+            }
+            let immune = cq!(hurtbox_query.get(b));
+            if immune {
+                commands.trigger(OnBlock(a, b));
+            } else {
                 commands.trigger(OnHit(a, b));
             }
         }
     }
 }
 
+/// An observable event for when a hitbox hits an immune hurtbox.
+#[derive(Event)]
+pub struct OnBlock(pub Entity, pub Entity);
+
 #[derive(Component, Reflect)]
 #[reflect(Component)]
 pub struct HurtSfx(pub Handle<AudioSource>, pub f64);
@@ -78,10 +89,13 @@ impl Configure for HurtSfx {
     }
 }
 
-fn play_hurt_sfx(trigger: Trigger<OnHit>, sfx_query: Query<&HurtSfx>, audio: Res<Audio>) {
-    let sfx = rq!(sfx_query.get(trigger.event().1));
-    audio
-        .play(sfx.0.clone())
-        .with_volume(sfx.1)
-        .with_playback_rate(rand::thread_rng().gen_range(0.7..1.6));
+fn play_hurt_sfx(
+    trigger: Trigger<OnHit>,
+    config: ConfigRef<AudioConfig>,
+    sfx_query: Query<&HurtSfx>,
+    mut sfx: Sfx,
+) {
+    let config = r!(config.get());
+    let hurt_sfx = rq!(sfx_query.get(trigger.event().1));
+    sfx.play_voice(hurt_sfx.0.clone(), hurt_sfx.1, &config.hurt_sfx);
 }
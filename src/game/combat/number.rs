@@ -0,0 +1,228 @@
+use bevy::ecs::system::EntityCommand;
+use bevy::ecs::system::SystemState;
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+use pyri_state::prelude::*;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::core::pause::Pause;
+use crate::core::settings::Settings;
+use crate::core::theme::ThemeConfig;
+use crate::core::UpdateSet;
+use crate::game::actor::health::OnHeal;
+use crate::game::actor::player::IsPlayer;
+use crate::game::combat::damage::OnDamage;
+use crate::game::combat::hit::OnBlock;
+use crate::game::GameRoot;
+use crate::ui::prelude::*;
+use crate::util::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.configure::<(ConfigHandle<NumberConfig>, FloatingNumber)>();
+}
+
+#[derive(Asset, Reflect, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NumberConfig {
+    /// How long a floating number lasts (in seconds).
+    pub lifetime_secs: f32,
+    /// How fast a floating number rises (in pixels per second).
+    pub rise_speed: f32,
+    /// The fraction of its lifetime after which a floating number starts to fade out.
+    pub fade_start: f32,
+    /// Damage below this amount is merged into a recent floating number on the same target.
+    pub merge_damage: f32,
+    /// How recent a floating number must be (in seconds) to merge into.
+    pub merge_secs: f32,
+    /// Damage at or above this amount is shown as a critical hit.
+    pub crit_damage: f32,
+}
+
+impl Config for NumberConfig {
+    const PATH: &'static str = "config/number.ron";
+    const EXTENSION: &'static str = "number.ron";
+}
+
+/// A world-space number that rises and fades out over an actor.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct FloatingNumber {
+    target: Entity,
+    color: ThemeColor,
+    /// The value shown, or `None` if the target was immune.
+    value: Option<f32>,
+    age: f32,
+}
+
+impl Configure for FloatingNumber {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.observe(number_on_damage);
+        app.observe(number_on_heal);
+        app.observe(number_on_block);
+        app.add_systems(
+            Update,
+            update_floating_numbers
+                .in_set(UpdateSet::Update)
+                .run_if(Pause::is_disabled),
+        );
+    }
+}
+
+impl FloatingNumber {
+    fn text(&self, locale: &Locale) -> String {
+        match self.value {
+            Some(value) => format!("{}", value.round()),
+            None => locale.format("number.immune", &[]),
+        }
+    }
+}
+
+fn floating_number(
+    target: Entity,
+    color: ThemeColor,
+    value: Option<f32>,
+    crit: bool,
+) -> impl EntityCommand {
+    move |entity: Entity, world: &mut World| {
+        let number = FloatingNumber {
+            target,
+            color,
+            value,
+            age: 0.0,
+        };
+        let (parent, translation, color_value, text) = {
+            let (theme, game_root, gt_query, locale) = SystemState::<(
+                ConfigRef<ThemeConfig>,
+                Res<GameRoot>,
+                Query<&GlobalTransform>,
+                Locale,
+            )>::new(world)
+            .get(world);
+            let theme = r!(theme.get());
+            let gt = r!(gt_query.get(target));
+
+            (
+                game_root.vfx,
+                gt.translation(),
+                r!(theme.active()).colors[color],
+                number.text(&locale),
+            )
+        };
+        let (font, font_size) = if crit {
+            (BOLD_FONT_HANDLE, 16.0)
+        } else {
+            (FONT_HANDLE, 8.0)
+        };
+
+        world
+            .entity_mut(entity)
+            .insert((
+                Name::new("FloatingNumber"),
+                Text2dBundle {
+                    text: Text::from_section(
+                        text,
+                        TextStyle {
+                            font,
+                            font_size,
+                            color: color_value,
+                        },
+                    ),
+                    text_anchor: Anchor::BottomCenter,
+                    transform: Transform::from_translation(
+                        translation.truncate().extend(10.0) + Vec3::Y * 4.0,
+                    ),
+                    ..default()
+                },
+                number,
+            ))
+            .set_parent(parent);
+    }
+}
+
+fn number_on_damage(
+    trigger: Trigger<OnDamage>,
+    mut commands: Commands,
+    config: ConfigRef<NumberConfig>,
+    settings: Res<Settings>,
+    locale: Locale,
+    player_query: Query<(), With<IsPlayer>>,
+    mut number_query: Query<(&mut FloatingNumber, &mut Text)>,
+) {
+    if !settings.damage_numbers {
+        return;
+    }
+    let config = r!(config.get());
+    let target = r!(trigger.get_entity());
+    let damage = trigger.event().0;
+    let color = if player_query.contains(target) {
+        ThemeColor::PlayerDamage
+    } else {
+        ThemeColor::Damage
+    };
+
+    // Merge small rapid hits into a recent floating number.
+    if damage < config.merge_damage {
+        for (mut number, mut text) in &mut number_query {
+            if number.target != target || number.color != color || number.age >= config.merge_secs {
+                continue;
+            }
+            let value = c!(number.value.as_mut());
+            *value += damage;
+            number.age = 0.0;
+            text.sections[0].value = number.text(&locale);
+            return;
+        }
+    }
+
+    let crit = damage >= config.crit_damage;
+    let color = if crit { ThemeColor::CritDamage } else { color };
+    commands.spawn_with(floating_number(target, color, Some(damage), crit));
+}
+
+fn number_on_heal(trigger: Trigger<OnHeal>, mut commands: Commands, settings: Res<Settings>) {
+    if !settings.damage_numbers {
+        return;
+    }
+    let target = r!(trigger.get_entity());
+    commands.spawn_with(floating_number(
+        target,
+        ThemeColor::Heal,
+        Some(trigger.event().0),
+        false,
+    ));
+}
+
+fn number_on_block(trigger: Trigger<OnBlock>, mut commands: Commands, settings: Res<Settings>) {
+    if !settings.damage_numbers {
+        return;
+    }
+    let &OnBlock(_, hurtbox) = trigger.event();
+    commands.spawn_with(floating_number(hurtbox, ThemeColor::Immune, None, false));
+}
+
+fn update_floating_numbers(
+    time: Res<Time>,
+    config: ConfigRef<NumberConfig>,
+    theme: ConfigRef<ThemeConfig>,
+    mut despawn: ResMut<LateDespawn>,
+    mut number_query: Query<(Entity, &mut FloatingNumber, &mut Transform, &mut Text)>,
+) {
+    let config = r!(config.get());
+    let palette = &r!(theme.get().and_then(ThemeConfig::active)).colors;
+    let dt = time.delta_seconds();
+
+    for (entity, mut number, mut transform, mut text) in &mut number_query {
+        number.age += dt;
+        if number.age >= config.lifetime_secs {
+            despawn.recursive(entity);
+            continue;
+        }
+
+        transform.translation.y += config.rise_speed * dt;
+        let t = number.age / config.lifetime_secs;
+        let alpha = 1.0 - ((t - config.fade_start) / (1.0 - config.fade_start)).max(0.0);
+        text.sections[0].style.color = palette[number.color].with_alpha(alpha);
+    }
+}
@@ -15,6 +15,8 @@ use crate::game::actor::attack::Attack;
 use crate::game::actor::attack::AttackController;
 use crate::game::actor::facing::Facing;
 use crate::game::actor::faction::Faction;
+use crate::game::audio::sfx::Sfx;
+use crate::game::audio::sfx::SfxStyle;
 use crate::game::card::attack::AimTowardsFacing;
 use crate::game::card::attack::AttackOnBeat;
 use crate::game::cleanup::DespawnOnHit;
@@ -23,6 +25,7 @@ use crate::game::cleanup::DespawnRadiusSq;
 use crate::game::combat::damage::HitboxDamage;
 use crate::game::combat::hit::Hitbox;
 use crate::game::combat::knockback::HitboxKnockback;
+use crate::game::vfx::ParticleEmitter;
 use crate::game::GameLayer;
 use crate::game::GameRoot;
 use crate::util::prelude::*;
@@ -88,6 +91,8 @@ pub struct Projectile {
     pub spawn_sfx: Option<Handle<AudioSource>>,
     #[serde(default = "one")]
     pub spawn_sfx_volume: f64,
+    #[serde(default)]
+    pub spawn_sfx_style: SfxStyle,
 
     /// Lifetime in seconds (not beats).
     pub lifetime: f32,
@@ -102,6 +107,9 @@ pub struct Projectile {
     pub knockback: f32,
     #[serde(default)]
     pub pierce: usize,
+    /// The particle emitter to leave a trail with.
+    #[serde(default)]
+    pub trail: Option<String>,
 }
 
 fn one() -> f64 {
@@ -124,8 +132,8 @@ pub fn projectile(
 
     move |entity: Entity, world: &mut World| {
         let mut system_state =
-            SystemState::<(ConfigRef<ProjectileConfig>, Res<GameRoot>, Res<Audio>)>::new(world);
-        let (config, game_root, audio) = system_state.get(world);
+            SystemState::<(ConfigRef<ProjectileConfig>, Res<GameRoot>, Sfx)>::new(world);
+        let (config, game_root, mut sfx) = system_state.get_mut(world);
         let config = r!(config.get());
         let projectile = r!(config.projectiles.get(&key)).clone();
         let parent = game_root.projectiles;
@@ -139,9 +147,11 @@ pub fn projectile(
         };
 
         if let (false, Faction::Player, Some(spawn_sfx)) = (mute, faction, projectile.spawn_sfx) {
-            audio
-                .play(spawn_sfx)
-                .with_volume(projectile.spawn_sfx_volume);
+            sfx.play(
+                spawn_sfx,
+                projectile.spawn_sfx_volume,
+                &projectile.spawn_sfx_style,
+            );
         }
 
         let mut entity = world.entity_mut(entity);
@@ -201,6 +211,10 @@ pub fn projectile(
             ))
             .set_parent(parent);
 
+        if let Some(trail) = &projectile.trail {
+            entity.insert(ParticleEmitter::new(trail).with_tint(color));
+        }
+
         if let Some((attack, facing)) = child_projectiles {
             entity.insert((
                 AttackOnBeat(attack.clone(), 4, 0),
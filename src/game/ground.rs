@@ -8,15 +8,82 @@ use bevy::sprite::Material2dPlugin;
 use bevy::sprite::MaterialMesh2dBundle;
 use bevy::time::Stopwatch;
 use pyri_state::prelude::*;
+use serde::Deserialize;
+use serde::Serialize;
 
 use crate::core::pause::Pause;
 use crate::core::UpdateSet;
+use crate::game::actor::level::Level;
+use crate::game::actor::player::IsPlayer;
 use crate::game::audio::music::on_full_beat;
 use crate::screen::Screen;
 use crate::util::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
-    app.configure::<Ground>();
+    app.configure::<(Ground, ConfigHandle<GroundConfig>)>();
+}
+
+#[derive(Asset, Reflect, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GroundConfig {
+    pub zoom: f32,
+    /// The number of grid cells per tile.
+    pub grid_ratio: f32,
+    pub grid_line_color: Color,
+    /// The alpha of the tiles between grid lines.
+    pub space_alpha: f32,
+    pub contrast: f32,
+    pub saturation: f32,
+    pub brightness: f32,
+    /// Reshuffle the tiles every four beats.
+    pub use_beat_input: bool,
+    /// Fade the tiles in and out over time.
+    pub animate: bool,
+    pub animation_speed: f32,
+    /// The palette to use at each player level (the last palette is used for all later levels).
+    pub palettes: Vec<GroundPalette>,
+}
+
+impl Config for GroundConfig {
+    const PATH: &'static str = "config/ground.ron";
+    const EXTENSION: &'static str = "ground.ron";
+
+    fn on_load(&mut self, world: &mut World) {
+        let ground = world.resource::<Ground>();
+        let material = ground.material.clone();
+        let level = ground.level;
+        let mut materials = world.resource_mut::<Assets<GroundMaterial>>();
+        let material = r!(materials.get_mut(&material));
+        self.apply(&mut material.uniforms, level);
+    }
+}
+
+impl GroundConfig {
+    fn apply(&self, uniforms: &mut Uniforms, level: usize) {
+        uniforms.zoom = self.zoom;
+        uniforms.grid_ratio = self.grid_ratio;
+        uniforms.grid_line_color = self.grid_line_color.to_linear().to_vec4();
+        uniforms.space_alpha = self.space_alpha;
+        uniforms.contrast = self.contrast;
+        uniforms.saturation = self.saturation;
+        uniforms.brightness = self.brightness;
+        uniforms.use_beat_input = self.use_beat_input as u32;
+        uniforms.animate = self.animate as u32;
+        uniforms.animation_speed = self.animation_speed;
+
+        let palette = r!(self.palettes.get(level).or(self.palettes.last()));
+        uniforms.background_color = palette.background.to_linear().to_vec4();
+        for (uniform, color) in uniforms.colors.iter_mut().zip(&palette.colors) {
+            *uniform = color.to_linear().to_vec4();
+        }
+    }
+}
+
+#[derive(Reflect, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GroundPalette {
+    pub background: Color,
+    pub colors: [Color; 4],
 }
 
 const GROUND_Z_INDEX: f32 = -10.0;
@@ -29,6 +96,8 @@ pub struct Ground {
     material: Handle<GroundMaterial>,
     mesh: Handle<Mesh>,
     animation_stopwatch: Stopwatch,
+    /// The player level that the ground's palette is for.
+    level: usize,
 }
 
 impl Configure for Ground {
@@ -43,6 +112,7 @@ impl Configure for Ground {
                 update_background_beat
                     .in_set(UpdateSet::Update)
                     .run_if(on_full_beat(4)),
+                update_background_palette.in_set(UpdateSet::SyncLate),
             )),
         );
     }
@@ -62,6 +132,7 @@ impl FromWorld for Ground {
             material,
             mesh,
             animation_stopwatch,
+            level: 0,
         }
     }
 }
@@ -78,6 +149,18 @@ struct Uniforms {
     pub camera_y: f32,
     pub random: f32,
     pub time: f32,
+    pub zoom: f32,
+    pub grid_ratio: f32,
+    pub space_alpha: f32,
+    pub contrast: f32,
+    pub saturation: f32,
+    pub brightness: f32,
+    pub use_beat_input: u32,
+    pub animate: u32,
+    pub animation_speed: f32,
+    pub grid_line_color: Vec4,
+    pub background_color: Vec4,
+    pub colors: [Vec4; 4],
 }
 
 impl Material2d for GroundMaterial {
@@ -131,3 +214,20 @@ fn update_background_beat(mut ground_material: ResMut<Assets<GroundMaterial>>) {
         material.uniforms.random = rand::random();
     }
 }
+
+fn update_background_palette(
+    config: ConfigRef<GroundConfig>,
+    mut ground_material: ResMut<Assets<GroundMaterial>>,
+    mut ground: ResMut<Ground>,
+    player_query: Query<&Level, (With<IsPlayer>, Changed<Level>)>,
+) {
+    let config = r!(config.get());
+    let level = rq!(player_query.get_single()).current;
+    if ground.level == level {
+        return;
+    }
+    ground.level = level;
+
+    let material = r!(ground_material.get_mut(&ground.material));
+    config.apply(&mut material.uniforms, level);
+}
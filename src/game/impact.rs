@@ -0,0 +1,90 @@
+//! Camera feedback for game events.
+
+use bevy::prelude::*;
+use pyri_state::prelude::*;
+
+use crate::core::camera::effect::CameraEffects;
+use crate::core::camera::CameraConfig;
+use crate::core::UpdateSet;
+use crate::game::actor::enemy::IsEnemy;
+use crate::game::actor::health::Health;
+use crate::game::actor::level::up::LevelUp;
+use crate::game::actor::player::IsPlayer;
+use crate::game::audio::music::Beat;
+use crate::game::combat::damage::OnDamage;
+use crate::game::combat::death::OnDeath;
+use crate::screen::Screen;
+use crate::util::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.observe(shake_on_player_hurt);
+    app.observe(hitstop_on_heavy_hit);
+    app.observe(shake_on_big_kill);
+
+    app.add_systems(
+        Update,
+        Screen::Playing.on_update((
+            punch_on_level_up
+                .in_set(UpdateSet::Update)
+                .run_if(on_event::<LevelUp>()),
+            punch_on_beat.in_set(UpdateSet::Update),
+        )),
+    );
+}
+
+fn shake_on_player_hurt(
+    trigger: Trigger<OnDamage>,
+    config: ConfigRef<CameraConfig>,
+    player_query: Query<(), With<IsPlayer>>,
+    mut effects: ResMut<CameraEffects>,
+) {
+    let config = r!(config.get());
+    let entity = r!(trigger.get_entity());
+    if player_query.contains(entity) {
+        effects.add_trauma(config.shake_hurt);
+    }
+}
+
+fn hitstop_on_heavy_hit(
+    trigger: Trigger<OnDamage>,
+    config: ConfigRef<CameraConfig>,
+    mut effects: ResMut<CameraEffects>,
+) {
+    let config = r!(config.get());
+    if trigger.event().0 >= config.hitstop_damage {
+        effects.hitstop(config.hitstop_secs);
+    }
+}
+
+fn shake_on_big_kill(
+    trigger: Trigger<OnDeath>,
+    config: ConfigRef<CameraConfig>,
+    enemy_query: Query<&Health, With<IsEnemy>>,
+    mut effects: ResMut<CameraEffects>,
+) {
+    let config = r!(config.get());
+    let entity = r!(trigger.get_entity());
+    let health = rq!(enemy_query.get(entity));
+    if health.max >= config.shake_kill_health {
+        effects.add_trauma(config.shake_kill);
+    }
+}
+
+fn punch_on_level_up(config: ConfigRef<CameraConfig>, mut effects: ResMut<CameraEffects>) {
+    let config = r!(config.get());
+    effects.punch(config.punch_level_up);
+}
+
+fn punch_on_beat(
+    config: ConfigRef<CameraConfig>,
+    beat: Res<Beat>,
+    mut effects: ResMut<CameraEffects>,
+) {
+    let config = r!(config.get());
+    if config.punch_beat_interval == 0 {
+        return;
+    }
+    if beat.crossed(8 * config.punch_beat_interval) {
+        effects.punch(config.punch_beat);
+    }
+}
@@ -0,0 +1,297 @@
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use pyri_state::prelude::*;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::core::UpdateSet;
+use crate::game::actor::faction::Faction;
+use crate::game::actor::level::xp::XpReward;
+use crate::game::actor::player::IsPlayer;
+use crate::game::audio::music::on_full_beat;
+use crate::game::audio::music::Beat;
+use crate::game::audio::timing::Combo;
+use crate::game::challenge::ActiveChallenge;
+use crate::game::combat::death::IsDead;
+use crate::game::combat::death::OnDeath;
+use crate::game::combat::hit::OnHit;
+use crate::screen::playing::victory_menu::EndlessMode;
+use crate::screen::playing::PlayingMenu;
+use crate::screen::Screen;
+use crate::util::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.configure::<(
+        ConfigHandle<ScoreConfig>,
+        Score,
+        IsScoreDisplay,
+        Leaderboards,
+    )>();
+}
+
+#[derive(Asset, Reflect, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScoreConfig {
+    // Scoring:
+    /// The points for a kill, per XP it rewards.
+    pub points_per_xp: f32,
+    /// The bonus for a kill per step of the current combo, as a fraction of the kill's points.
+    pub combo_bonus: f32,
+    /// The maximum combo bonus, as a fraction of the kill's points.
+    pub combo_bonus_cap: f32,
+    /// The points for each beat survived.
+    pub points_per_beat: f32,
+    /// The number of beats to survive without getting hit to earn a no-hit bonus.
+    pub no_hit_beats: usize,
+    pub no_hit_bonus: f32,
+    pub victory_bonus: f32,
+
+    // Leaderboards:
+    /// The number of entries to keep on each leaderboard.
+    pub leaderboard_size: usize,
+    /// The maximum number of characters in a leaderboard name.
+    pub max_name_length: usize,
+}
+
+impl Config for ScoreConfig {
+    const PATH: &'static str = "config/score.ron";
+    const EXTENSION: &'static str = "score.ron";
+}
+
+/// The score for the current run, broken down by source.
+#[derive(Resource, Reflect, Default, Copy, Clone)]
+#[reflect(Resource)]
+pub struct Score {
+    pub kills: f32,
+    pub combo: f32,
+    pub survival: f32,
+    pub no_hit: f32,
+    pub victory: f32,
+    /// The number of beats since the player was last hit.
+    pub hitless_beats: usize,
+    /// The leaderboard this run was submitted to, so it's only submitted once per mode.
+    pub submitted: Option<RunMode>,
+}
+
+impl Configure for Score {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.init_resource::<Self>();
+        app.add_systems(
+            StateFlush,
+            (
+                Screen::Playing.on_enter(reset_score),
+                PlayingMenu::Victory.on_enter(award_victory_bonus),
+            ),
+        );
+        app.add_systems(
+            Update,
+            Screen::Playing.on_update(score_beat.in_set(UpdateSet::Update).run_if(on_full_beat(1))),
+        );
+        app.observe(score_kill);
+        app.observe(reset_hitless_beats);
+    }
+}
+
+impl Score {
+    pub fn total(&self) -> u64 {
+        (self.kills + self.combo + self.survival + self.no_hit + self.victory).round() as u64
+    }
+}
+
+fn reset_score(mut score: ResMut<Score>) {
+    *score = default();
+}
+
+pub fn award_victory_bonus(config: ConfigRef<ScoreConfig>, mut score: ResMut<Score>) {
+    let config = r!(config.get());
+    score.victory += config.victory_bonus;
+}
+
+fn score_beat(
+    config: ConfigRef<ScoreConfig>,
+    beat: Res<Beat>,
+    player_query: Query<(), (With<IsPlayer>, Without<IsDead>)>,
+    mut score: ResMut<Score>,
+) {
+    let config = r!(config.get());
+    if player_query.is_empty() {
+        return;
+    }
+
+    // More than one beat can be crossed in a single frame.
+    for _ in 0..beat.crossings(8) {
+        score.survival += config.points_per_beat;
+        score.hitless_beats += 1;
+        if config.no_hit_beats > 0 && score.hitless_beats % config.no_hit_beats == 0 {
+            score.no_hit += config.no_hit_bonus;
+        }
+    }
+}
+
+fn score_kill(
+    trigger: Trigger<OnDeath>,
+    config: ConfigRef<ScoreConfig>,
+    death_query: Query<(&Faction, &XpReward)>,
+    combo: Res<Combo>,
+    mut score: ResMut<Score>,
+) {
+    let entity = r!(trigger.get_entity());
+    let (faction, reward) = rq!(death_query.get(entity));
+    if !faction.is_enemy() {
+        return;
+    }
+
+    let config = r!(config.get());
+    let points = reward.0 * config.points_per_xp;
+    score.kills += points;
+    score.combo += points * (combo.count as f32 * config.combo_bonus).min(config.combo_bonus_cap);
+}
+
+fn reset_hitless_beats(
+    trigger: Trigger<OnHit>,
+    player_query: Query<(), With<IsPlayer>>,
+    mut score: ResMut<Score>,
+) {
+    if player_query.contains(trigger.event().1) {
+        score.hitless_beats = 0;
+    }
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct IsScoreDisplay;
+
+impl Configure for IsScoreDisplay {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(
+            Update,
+            update_score_display
+                .in_set(UpdateSet::SyncLate)
+                .run_if(resource_changed::<Score>),
+        );
+    }
+}
+
+fn update_score_display(score: Res<Score>, mut text_query: Query<&mut Text, With<IsScoreDisplay>>) {
+    for mut text in &mut text_query {
+        c!(text.sections.get_mut(1)).value = score.total().to_string();
+    }
+}
+
+/// The kind of run, each with its own leaderboard.
+#[derive(Reflect, Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum RunMode {
+    Normal,
+    /// An afterparty after winning a normal run.
+    Endless,
+    Challenge,
+}
+
+impl RunMode {
+    pub fn current(challenge: &ActiveChallenge, endless_mode: &EndlessMode) -> Self {
+        if challenge.0.is_some() {
+            Self::Challenge
+        } else if endless_mode.0 {
+            Self::Endless
+        } else {
+            Self::Normal
+        }
+    }
+
+    /// The localization key for this mode's leaderboard.
+    pub fn leaderboard_key(self) -> &'static str {
+        match self {
+            Self::Normal => "leaderboard.normal",
+            Self::Endless => "leaderboard.endless",
+            Self::Challenge => "leaderboard.challenge",
+        }
+    }
+}
+
+#[derive(Reflect, Serialize, Deserialize, Clone)]
+pub struct LeaderboardEntry {
+    pub name: String,
+    pub score: u64,
+}
+
+/// The top scores for each mode, saved between sessions.
+#[derive(Resource, Reflect, Serialize, Deserialize, Default)]
+#[reflect(Resource)]
+#[serde(default)]
+pub struct Leaderboards {
+    /// The entries for each mode, from highest to lowest score.
+    pub boards: HashMap<RunMode, Vec<LeaderboardEntry>>,
+    /// The last name entered, to fill in next time.
+    pub last_name: String,
+}
+
+impl Configure for Leaderboards {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.configure::<Persistent<Self>>();
+    }
+}
+
+impl Persist for Leaderboards {
+    const KEY: &'static str = "leaderboards";
+}
+
+impl Leaderboards {
+    pub fn board(&self, mode: RunMode) -> &[LeaderboardEntry] {
+        self.boards.get(&mode).map_or(&[], |x| x)
+    }
+
+    /// The index a score would be placed at, if it would make the leaderboard.
+    /// Ties go to the earlier entry.
+    pub fn rank(&self, mode: RunMode, score: u64, size: usize) -> Option<usize> {
+        let rank = self
+            .board(mode)
+            .iter()
+            .take_while(|x| x.score >= score)
+            .count();
+        (rank < size).then_some(rank)
+    }
+
+    /// Add an entry to a leaderboard, and return its index if it made the leaderboard.
+    pub fn insert(&mut self, mode: RunMode, entry: LeaderboardEntry, size: usize) -> Option<usize> {
+        let rank = self.rank(mode, entry.score, size)?;
+        let board = self.boards.entry(mode).or_default();
+        board.insert(rank, entry);
+        board.truncate(size);
+        Some(rank)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(score: u64) -> LeaderboardEntry {
+        LeaderboardEntry {
+            name: score.to_string(),
+            score,
+        }
+    }
+
+    fn scores(leaderboards: &Leaderboards, mode: RunMode) -> Vec<u64> {
+        leaderboards.board(mode).iter().map(|x| x.score).collect()
+    }
+
+    #[test]
+    fn test_leaderboard_insert() {
+        let mut leaderboards = Leaderboards::default();
+        for score in [30, 10, 20] {
+            leaderboards.insert(RunMode::Normal, entry(score), 3);
+        }
+        assert_eq!(scores(&leaderboards, RunMode::Normal), [30, 20, 10]);
+        assert!(leaderboards.board(RunMode::Endless).is_empty());
+
+        // Ties go to the earlier entry, and the lowest score falls off.
+        assert_eq!(leaderboards.insert(RunMode::Normal, entry(20), 3), Some(2));
+        assert_eq!(scores(&leaderboards, RunMode::Normal), [30, 20, 20]);
+        assert_eq!(leaderboards.insert(RunMode::Normal, entry(5), 3), None);
+        assert_eq!(leaderboards.rank(RunMode::Normal, 40, 3), Some(0));
+    }
+}
@@ -11,6 +11,8 @@ use serde::Serialize;
 
 use crate::core::camera::CameraRoot;
 use crate::core::pause::Pause;
+use crate::core::theme::Theme;
+use crate::core::theme::ThemeConfig;
 use crate::core::PostColorSet;
 use crate::core::PostTransformSet;
 use crate::core::UpdateSet;
@@ -53,23 +55,22 @@ pub struct SpotlightConfig {
 impl Config for SpotlightConfig {
     const PATH: &'static str = "config/spotlight.ron";
     const EXTENSION: &'static str = "spotlight.ron";
-
-    fn on_load(&mut self, _world: &mut World) {
-        for color in &mut self.color_loop {
-            color.set_alpha(color.alpha() * self.alpha_multiplier);
-        }
-    }
 }
 
 impl SpotlightConfig {
-    fn color(&self, t: f32) -> Color {
-        let n = self.color_loop.len();
+    fn color(&self, theme: &Theme, t: f32) -> Color {
+        let color_loop = theme
+            .spotlight_color_loop
+            .as_ref()
+            .unwrap_or(&self.color_loop);
+        let n = color_loop.len();
         let t = (t * n as f32).rem_euclid(n as f32);
         let lo = t as usize;
         let hi = if lo + 1 < n { lo + 1 } else { 0 };
         let t = t.fract().quadratic_in_out();
 
-        self.color_loop[lo].better_mix(&self.color_loop[hi], t)
+        let color = color_loop[lo].better_mix(&color_loop[hi], t);
+        color.with_alpha(color.alpha() * self.alpha_multiplier)
     }
 }
 
@@ -112,11 +113,13 @@ fn tick_spotlight(time: Res<Time>, mut spotlight_query: Query<&mut Spotlight>) {
 
 fn update_spotlight_color(
     config: ConfigRef<SpotlightConfig>,
+    theme: ConfigRef<ThemeConfig>,
     mut spotlight_query: Query<(&Spotlight, &mut Sprite)>,
 ) {
     let config = r!(config.get());
+    let theme = r!(theme.get().and_then(ThemeConfig::active));
     for (spotlight, mut sprite) in &mut spotlight_query {
-        sprite.color = config.color(spotlight.color_loop_t);
+        sprite.color = config.color(theme, spotlight.color_loop_t);
     }
 }
 
@@ -44,7 +44,7 @@ impl SpriteAnimation {
     }
 
     /// Calculate the texture atlas index of the animation after `beats` beats.
-    fn index(&self, beats: usize) -> usize {
+    pub fn index(&self, beats: usize) -> usize {
         let mut beats = beats % self.total_beats;
         let mut i = 0;
         while beats >= self.frames[i].beats {
@@ -1,11 +1,12 @@
 use bevy::ecs::system::EntityCommand;
-use bevy::ecs::system::SystemState;
 use bevy::prelude::*;
+use bevy::utils::HashMap;
 
 use crate::core::UpdateSet;
+use crate::game::actor::enemy::EnemyKey;
 use crate::game::actor::faction::Faction;
 use crate::game::audio::music::on_beat;
-use crate::game::audio::AudioConfig;
+use crate::game::audio::music::Beat;
 use crate::game::card::action::CardActionKey;
 use crate::game::card::CardConfig;
 use crate::game::card::OnPlayCard;
@@ -17,11 +18,17 @@ pub(super) fn plugin(app: &mut App) {
     app.configure::<Stats>();
 }
 
-#[derive(Resource, Reflect, Default, Copy, Clone)]
+/// Everything the player has done during the current run.
+#[derive(Resource, Reflect, Default, Clone)]
 #[reflect(Resource)]
 pub struct Stats {
     pub beats: usize,
-    pub kills: usize,
+    /// The duration (in seconds) of the beats counted, at the tempo they were heard.
+    pub seconds: f64,
+    /// Enemies killed, by key in `ActorConfig::enemies`.
+    pub kills: HashMap<String, usize>,
+    /// Cards played by the player, by key in `CardConfig`.
+    pub plays: HashMap<String, usize>,
     pub played_moves: usize,
     pub played_attacks: usize,
     pub played_heals: usize,
@@ -40,22 +47,21 @@ impl Configure for Stats {
     }
 }
 
-fn count_beats(mut stats: ResMut<Stats>) {
+impl Stats {
+    pub fn total_kills(&self) -> usize {
+        self.kills.values().sum()
+    }
+}
+
+fn count_beats(beat: Res<Beat>, mut stats: ResMut<Stats>) {
     stats.beats += 1;
+    stats.seconds += beat.beats_to_secs(1.0);
 }
 
-fn count_kills(
-    trigger: Trigger<OnDeath>,
-    faction_query: Query<&Faction>,
-    mut stats: ResMut<Stats>,
-) {
+fn count_kills(trigger: Trigger<OnDeath>, enemy_query: Query<&EnemyKey>, mut stats: ResMut<Stats>) {
     let entity = r!(trigger.get_entity());
-    let faction = r!(faction_query.get(entity));
-    if !faction.is_enemy() {
-        return;
-    }
-
-    stats.kills += 1;
+    let key = rq!(enemy_query.get(entity));
+    *stats.kills.entry(key.0.clone()).or_default() += 1;
 }
 
 fn count_played_cards(
@@ -71,7 +77,9 @@ fn count_played_cards(
     }
 
     let config = r!(config.get());
-    let card = r!(config.card_map.get(&trigger.event().0));
+    let key = &trigger.event().0;
+    let card = r!(config.card_map.get(key));
+    *stats.plays.entry(key.clone()).or_default() += 1;
     match card.action_key {
         CardActionKey::Attack => stats.played_attacks += 1,
         CardActionKey::Heal => stats.played_heals += 1,
@@ -81,24 +89,17 @@ fn count_played_cards(
 
 impl EntityCommand for Stats {
     fn apply(self, id: Entity, world: &mut World) {
-        let mut system_state = SystemState::<(ConfigRef<AudioConfig>, Res<Stats>)>::new(world);
-        let (audio_config, stats) = system_state.get(world);
-        let audio_config = r!(audio_config.get());
         let stats = [
-            format!(
-                "[b]{:.0}",
-                stats.beats as f64 / 8.0 * 60.0 / audio_config.music_bpm
-                    + audio_config.music_zeroth_beat
-            ),
-            "seconds partied".to_string(),
-            format!("[b]{}", stats.kills),
-            "blobos impressed".to_string(),
-            format!("[b]{}", stats.played_moves),
-            "dances performed".to_string(),
-            format!("[b]{}", stats.played_attacks),
-            "notes played".to_string(),
-            format!("[b]{}", stats.played_heals),
-            "rests taken".to_string(),
+            LocalizedText::rich("stats.value").with_args([format!("{:.0}", self.seconds)]),
+            LocalizedText::rich("stats.seconds"),
+            LocalizedText::rich("stats.value").with_args([self.total_kills()]),
+            LocalizedText::rich("stats.kills"),
+            LocalizedText::rich("stats.value").with_args([self.played_moves]),
+            LocalizedText::rich("stats.moves"),
+            LocalizedText::rich("stats.value").with_args([self.played_attacks]),
+            LocalizedText::rich("stats.attacks"),
+            LocalizedText::rich("stats.value").with_args([self.played_heals]),
+            LocalizedText::rich("stats.heals"),
         ];
 
         world
@@ -120,7 +121,7 @@ impl EntityCommand for Stats {
                 for (i, text) in stats.into_iter().enumerate() {
                     children.spawn((
                         Name::new(format!("StatsSpan{}", i)),
-                        TextBundle::from_sections(parse_rich(&text)).with_style(Style {
+                        TextBundle::default().with_style(Style {
                             justify_self: if i % 2 == 0 {
                                 JustifySelf::End
                             } else {
@@ -128,6 +129,7 @@ impl EntityCommand for Stats {
                             },
                             ..default()
                         }),
+                        text,
                         DynamicFontSize::new(Vw(3.0)).with_step(8.0),
                         ThemeColorForText(vec![if i % 2 == 0 {
                             ThemeColor::Indicator
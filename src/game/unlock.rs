@@ -0,0 +1,173 @@
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use bevy::utils::HashSet;
+use pyri_state::prelude::*;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::game::actor::level::Level;
+use crate::game::actor::player::IsPlayer;
+use crate::game::actor::Actor;
+use crate::game::actor::ActorConfig;
+use crate::game::card::Card;
+use crate::game::card::CardConfig;
+use crate::game::stats::Stats;
+use crate::screen::playing::PlayingMenu;
+use crate::screen::Screen;
+use crate::ui::prelude::*;
+use crate::util::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.configure::<(Unlocks, RecordedStats)>();
+}
+
+/// A condition to unlock a card or actor, checked at the end of each run.
+/// Locked cards aren't offered on level up, and locked actors don't spawn.
+#[derive(Reflect, Serialize, Deserialize, Clone)]
+pub enum Unlock {
+    /// Reach a level in a single run.
+    Level(usize),
+    /// Win a run.
+    Win,
+    /// Kill a number of an enemy, across all runs.
+    Kill(String, usize),
+    /// Play a card a number of times, across all runs.
+    Play(String, usize),
+}
+
+/// Lifetime progress towards unlocks, and what's been unlocked so far, saved between sessions.
+#[derive(Resource, Reflect, Serialize, Deserialize, Default)]
+#[reflect(Resource)]
+#[serde(default)]
+pub struct Unlocks {
+    pub best_level: usize,
+    pub wins: usize,
+    /// The number of kills by enemy key.
+    pub kills: HashMap<String, usize>,
+    /// The number of plays by card key.
+    pub plays: HashMap<String, usize>,
+    pub cards: HashSet<String>,
+    pub actors: HashSet<String>,
+}
+
+impl Configure for Unlocks {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.configure::<Persistent<Self>>();
+        app.add_systems(
+            StateFlush,
+            (
+                PlayingMenu::Victory.on_enter((record_win, record_run).chain()),
+                PlayingMenu::Defeat.on_enter(record_run),
+                // Record a run that was abandoned (e.g. quit from the pause menu).
+                Screen::Playing.on_exit(record_run),
+            ),
+        );
+    }
+}
+
+impl Persist for Unlocks {
+    const KEY: &'static str = "unlocks";
+}
+
+impl Unlocks {
+    pub fn has_card(&self, key: &str, card: &Card) -> bool {
+        card.unlock.is_none() || self.cards.contains(key)
+    }
+
+    pub fn has_actor(&self, key: &str, actor: &Actor) -> bool {
+        actor.unlock.is_none() || self.actors.contains(key)
+    }
+
+    fn is_met(&self, unlock: &Unlock) -> bool {
+        match unlock {
+            Unlock::Level(level) => self.best_level >= *level,
+            Unlock::Win => self.wins > 0,
+            Unlock::Kill(key, count) => self.kills.get(key).copied().unwrap_or_default() >= *count,
+            Unlock::Play(key, count) => self.plays.get(key).copied().unwrap_or_default() >= *count,
+        }
+    }
+}
+
+/// The run [`Stats`] that have already been added to [`Unlocks`].
+/// This way, a run that continues after a victory isn't counted twice.
+#[derive(Resource, Default)]
+struct RecordedStats(Stats);
+
+impl Configure for RecordedStats {
+    fn configure(app: &mut App) {
+        app.init_resource::<Self>();
+        app.add_systems(StateFlush, Screen::Playing.on_enter(reset_recorded_stats));
+    }
+}
+
+fn reset_recorded_stats(mut recorded: ResMut<RecordedStats>) {
+    *recorded = default();
+}
+
+fn record_win(mut unlocks: ResMut<Unlocks>) {
+    unlocks.wins += 1;
+}
+
+/// Add the counts in `now` that weren't in `before` to `total`.
+fn add_new_counts(
+    total: &mut HashMap<String, usize>,
+    now: &HashMap<String, usize>,
+    before: &HashMap<String, usize>,
+) {
+    for (key, &count) in now {
+        let new = count.saturating_sub(before.get(key).copied().unwrap_or_default());
+        *total.entry(key.clone()).or_default() += new;
+    }
+}
+
+/// Add the run so far to [`Unlocks`], and show a toast for anything newly unlocked.
+fn record_run(
+    mut commands: Commands,
+    stats: Res<Stats>,
+    mut recorded: ResMut<RecordedStats>,
+    mut unlocks: ResMut<Unlocks>,
+    card_config: ConfigRef<CardConfig>,
+    actor_config: ConfigRef<ActorConfig>,
+    level_query: Query<&Level, With<IsPlayer>>,
+    locale: Locale,
+) {
+    let recorded = std::mem::replace(&mut recorded.0, stats.clone());
+    let level = level_query
+        .iter()
+        .map(|x| x.current)
+        .max()
+        .unwrap_or_default();
+    unlocks.best_level = unlocks.best_level.max(level);
+    let unlocks = &mut *unlocks;
+    add_new_counts(&mut unlocks.kills, &stats.kills, &recorded.kills);
+    add_new_counts(&mut unlocks.plays, &stats.plays, &recorded.plays);
+
+    let mut unlocked = vec![];
+    if let Some(config) = card_config.get() {
+        for (key, card) in &config.card_map {
+            let unlock = cq!(card.unlock.as_ref());
+            if unlocks.cards.contains(key) || !unlocks.is_met(unlock) {
+                continue;
+            }
+
+            unlocks.cards.insert(key.clone());
+            unlocked.push(card.localized_name(key, &locale));
+        }
+    }
+    if let Some(config) = actor_config.get() {
+        for (key, actor) in config.players.iter().chain(&config.enemies) {
+            let unlock = cq!(actor.unlock.as_ref());
+            if unlocks.actors.contains(key) || !unlocks.is_met(unlock) {
+                continue;
+            }
+
+            unlocks.actors.insert(key.clone());
+            unlocked.push(actor.localized_name(key, &locale));
+        }
+    }
+
+    for name in unlocked {
+        commands.spawn_with(toast(LocalizedText::rich("unlock.toast").with_args([name])));
+    }
+}
@@ -0,0 +1,418 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use pyri_state::prelude::*;
+use rand::seq::SliceRandom as _;
+use rand::Rng as _;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::core::pause::Pause;
+use crate::core::UpdateSet;
+use crate::game::actor::level::up::LevelUp;
+use crate::game::audio::music::Beat;
+use crate::game::combat::hit::OnHit;
+use crate::game::GameRoot;
+use crate::screen::Screen;
+use crate::util::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.configure::<(
+        ConfigHandle<VfxConfig>,
+        ParticlePool,
+        Particle,
+        ParticleEmitter,
+        ParticleBurst,
+    )>();
+
+    app.observe(burst_on_hit);
+    app.add_systems(
+        Update,
+        Screen::Playing.on_update(
+            burst_on_level_up
+                .in_set(UpdateSet::Update)
+                .run_if(on_event::<LevelUp>()),
+        ),
+    );
+}
+
+#[derive(Asset, Reflect, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct VfxConfig {
+    /// The maximum number of particles alive at once.
+    pub pool_size: usize,
+    /// The emitter to burst when a hitbox hits a hurtbox.
+    #[serde(default)]
+    pub hit: Option<String>,
+    /// The emitter to burst when an actor levels up.
+    #[serde(default)]
+    pub level_up: Option<String>,
+    pub emitters: HashMap<String, Emitter>,
+}
+
+impl Config for VfxConfig {
+    const PATH: &'static str = "config/vfx.ron";
+    const EXTENSION: &'static str = "vfx.ron";
+}
+
+#[derive(Reflect, Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields, default)]
+pub struct Emitter {
+    /// The number of particles to emit all at once in a burst.
+    pub burst: usize,
+    /// The number of particles to emit per second.
+    pub rate: f32,
+    /// The number of particles to emit every `beat_division` eighth-beats.
+    pub beat_count: usize,
+    pub beat_division: usize,
+
+    /// Particle lifetime in seconds.
+    pub lifetime_lo: f32,
+    pub lifetime_hi: f32,
+    pub speed_lo: f32,
+    pub speed_hi: f32,
+    /// The direction to emit particles in (in turns).
+    pub direction: f32,
+    /// The range of directions around `direction` to emit particles in (in turns).
+    pub spread: f32,
+    /// The fraction of velocity remaining after 1 second.
+    pub drag: f32,
+    pub size: f32,
+
+    /// The start color of each particle is chosen randomly from this list (or the tint if empty).
+    pub colors: Vec<Color>,
+    /// The end color of each particle (or the start color, faded out, if not set).
+    pub end_color: Option<Color>,
+}
+
+impl Default for Emitter {
+    fn default() -> Self {
+        Self {
+            burst: 0,
+            rate: 0.0,
+            beat_count: 0,
+            beat_division: 8,
+            lifetime_lo: 0.3,
+            lifetime_hi: 0.5,
+            speed_lo: 20.0,
+            speed_hi: 40.0,
+            direction: 0.0,
+            spread: 1.0,
+            drag: 0.1,
+            size: 1.0,
+            colors: vec![],
+            end_color: None,
+        }
+    }
+}
+
+/// A pool of particle entities to reuse instead of spawning and despawning every frame.
+#[derive(Resource, Reflect, Default)]
+#[reflect(Resource)]
+pub struct ParticlePool {
+    free: Vec<Entity>,
+}
+
+impl Configure for ParticlePool {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.init_resource::<Self>();
+        app.add_systems(
+            StateFlush,
+            (
+                // The particles themselves are despawned with the `GameRoot`.
+                Screen::ANY.on_exit(clear_particle_pool),
+                Screen::Playing.on_enter(fill_particle_pool),
+            ),
+        );
+    }
+}
+
+fn clear_particle_pool(mut pool: ResMut<ParticlePool>) {
+    pool.free.clear();
+}
+
+fn fill_particle_pool(
+    mut commands: Commands,
+    config: ConfigRef<VfxConfig>,
+    mut pool: ResMut<ParticlePool>,
+) {
+    let config = r!(config.get());
+    for _ in 0..config.pool_size {
+        pool.free.push(commands.spawn_with(particle).id());
+    }
+}
+
+impl ParticlePool {
+    /// Emit `count` particles from an emitter, or as many as the pool allows.
+    fn emit(
+        &mut self,
+        emitter: &Emitter,
+        count: usize,
+        translation: Vec3,
+        tint: Option<Color>,
+        particle_query: &mut Query<ParticleData>,
+    ) {
+        let mut rng = rand::thread_rng();
+        for _ in 0..count {
+            // Only take a particle from the pool once it's known to exist.
+            let &entity = rq!(self.free.last());
+            let (mut particle, mut transform, mut sprite, mut visibility) =
+                rq!(particle_query.get_mut(entity));
+            self.free.pop();
+
+            let start_color = emitter
+                .colors
+                .choose(&mut rng)
+                .copied()
+                .or(tint)
+                .unwrap_or(Color::WHITE);
+            let angle = emitter.direction + emitter.spread * rng.gen_range(-0.5..=0.5);
+            let speed = rng.gen_range(
+                emitter.speed_lo.min(emitter.speed_hi)..=emitter.speed_lo.max(emitter.speed_hi),
+            );
+
+            *particle = Particle {
+                velocity: speed * Vec2::from_angle(angle * TAU),
+                drag: emitter.drag,
+                age: 0.0,
+                lifetime: rng.gen_range(
+                    emitter.lifetime_lo.min(emitter.lifetime_hi)
+                        ..=emitter.lifetime_lo.max(emitter.lifetime_hi),
+                ),
+                start_color,
+                end_color: emitter
+                    .end_color
+                    .unwrap_or_else(|| start_color.with_alpha(0.0)),
+            };
+            transform.translation = translation;
+            sprite.color = start_color;
+            sprite.custom_size = Some(Vec2::splat(emitter.size));
+            *visibility = Visibility::Inherited;
+        }
+    }
+}
+
+type ParticleData = (
+    &'static mut Particle,
+    &'static mut Transform,
+    &'static mut Sprite,
+    &'static mut Visibility,
+);
+
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct Particle {
+    velocity: Vec2,
+    drag: f32,
+    age: f32,
+    /// The particle is inactive (in the pool) if its lifetime is 0.
+    lifetime: f32,
+    start_color: Color,
+    end_color: Color,
+}
+
+impl Configure for Particle {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(
+            Update,
+            update_particles
+                .in_set(UpdateSet::Update)
+                .run_if(Pause::is_disabled),
+        );
+    }
+}
+
+fn particle(mut entity: EntityWorldMut) {
+    let parent = entity.world().resource::<GameRoot>().vfx;
+    entity
+        .insert((
+            Name::new("Particle"),
+            SpriteBundle {
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+            Particle::default(),
+        ))
+        .set_parent(parent);
+}
+
+fn update_particles(
+    time: Res<Time>,
+    mut pool: ResMut<ParticlePool>,
+    mut particle_query: Query<(Entity, ParticleData)>,
+) {
+    let dt = time.delta_seconds();
+
+    for (entity, (mut particle, mut transform, mut sprite, mut visibility)) in &mut particle_query {
+        if particle.lifetime <= 0.0 {
+            continue;
+        }
+
+        particle.age += dt;
+        if particle.age >= particle.lifetime {
+            particle.lifetime = 0.0;
+            *visibility = Visibility::Hidden;
+            pool.free.push(entity);
+            continue;
+        }
+
+        transform.translation += (particle.velocity * dt).extend(0.0);
+        particle.velocity *= particle.drag.powf(dt);
+        sprite.color = particle
+            .start_color
+            .better_mix(&particle.end_color, particle.age / particle.lifetime);
+    }
+}
+
+/// Continuously emit particles from an entity.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct ParticleEmitter {
+    pub key: String,
+    pub tint: Option<Color>,
+    /// Accumulated fractional particles to emit.
+    debt: f32,
+}
+
+impl Configure for ParticleEmitter {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(
+            Update,
+            update_particle_emitters
+                .in_set(UpdateSet::Spawn)
+                .run_if(Pause::is_disabled),
+        );
+    }
+}
+
+impl ParticleEmitter {
+    pub fn new(key: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            tint: None,
+            debt: 0.0,
+        }
+    }
+
+    pub fn with_tint(mut self, tint: impl Into<Color>) -> Self {
+        self.tint = Some(tint.into());
+        self
+    }
+}
+
+fn update_particle_emitters(
+    config: ConfigRef<VfxConfig>,
+    time: Res<Time>,
+    beat: Res<Beat>,
+    mut pool: ResMut<ParticlePool>,
+    mut emitter_query: Query<(&mut ParticleEmitter, &GlobalTransform)>,
+    mut particle_query: Query<ParticleData>,
+) {
+    let config = r!(config.get());
+    let dt = time.delta_seconds();
+
+    for (mut emitter, gt) in &mut emitter_query {
+        let data = c!(config.emitters.get(&emitter.key));
+
+        emitter.debt += data.rate * dt;
+        let mut count = emitter.debt as usize;
+        emitter.debt -= count as f32;
+        if data.beat_count > 0 && beat.crossed(data.beat_division.max(1)) {
+            count += data.beat_count;
+        }
+
+        pool.emit(
+            data,
+            count,
+            gt.translation(),
+            emitter.tint,
+            &mut particle_query,
+        );
+    }
+}
+
+/// An event to emit a single burst of particles.
+#[derive(Event)]
+pub struct ParticleBurst {
+    pub key: String,
+    pub translation: Vec3,
+    pub tint: Option<Color>,
+}
+
+impl Configure for ParticleBurst {
+    fn configure(app: &mut App) {
+        app.add_event::<Self>();
+        app.add_systems(Update, emit_particle_bursts.in_set(UpdateSet::Spawn));
+    }
+}
+
+impl ParticleBurst {
+    pub fn new(key: impl Into<String>, translation: Vec3) -> Self {
+        Self {
+            key: key.into(),
+            translation,
+            tint: None,
+        }
+    }
+
+    pub fn with_tint(mut self, tint: impl Into<Color>) -> Self {
+        self.tint = Some(tint.into());
+        self
+    }
+}
+
+fn emit_particle_bursts(
+    config: ConfigRef<VfxConfig>,
+    mut burst_events: EventReader<ParticleBurst>,
+    mut pool: ResMut<ParticlePool>,
+    mut particle_query: Query<ParticleData>,
+) {
+    let config = r!(config.get());
+    for event in burst_events.read() {
+        let data = c!(config.emitters.get(&event.key));
+        pool.emit(
+            data,
+            data.burst,
+            event.translation,
+            event.tint,
+            &mut particle_query,
+        );
+    }
+}
+
+fn burst_on_hit(
+    trigger: Trigger<OnHit>,
+    config: ConfigRef<VfxConfig>,
+    mut burst_events: EventWriter<ParticleBurst>,
+    hitbox_query: Query<&Sprite>,
+    hurtbox_query: Query<&GlobalTransform>,
+) {
+    let config = r!(config.get());
+    let key = rq!(config.hit.as_ref());
+    let &OnHit(hitbox, hurtbox) = trigger.event();
+    let gt = r!(hurtbox_query.get(hurtbox));
+
+    let mut burst = ParticleBurst::new(key, gt.translation());
+    if let Ok(sprite) = hitbox_query.get(hitbox) {
+        burst = burst.with_tint(sprite.color);
+    }
+    burst_events.send(burst);
+}
+
+fn burst_on_level_up(
+    config: ConfigRef<VfxConfig>,
+    mut level_up_events: EventReader<LevelUp>,
+    mut burst_events: EventWriter<ParticleBurst>,
+    actor_query: Query<&GlobalTransform>,
+) {
+    let config = r!(config.get());
+    let key = rq!(config.level_up.as_ref());
+    for &LevelUp(entity) in level_up_events.read() {
+        let gt = c!(actor_query.get(entity));
+        burst_events.send(ParticleBurst::new(key, gt.translation()));
+    }
+}
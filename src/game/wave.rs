@@ -10,14 +10,18 @@ use crate::core::UpdateSet;
 use crate::game::actor::enemy::enemy;
 use crate::game::actor::enemy::IsEnemy;
 use crate::game::actor::level::Level;
+use crate::game::actor::player::PlayerKey;
 use crate::game::actor::ActorConfig;
 use crate::game::audio::music::on_full_beat;
+use crate::game::challenge::RunModifiers;
+use crate::game::challenge::RunRng;
+use crate::game::unlock::Unlocks;
 use crate::screen::playing::victory_menu::EndlessMode;
 use crate::screen::Screen;
 use crate::util::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
-    app.configure::<(ConfigHandle<WaveConfig>, Wave)>();
+    app.configure::<(ConfigHandle<WaveConfig>, Wave, IsBoss)>();
 }
 
 #[derive(Asset, Reflect, Serialize, Deserialize)]
@@ -27,6 +31,17 @@ pub struct WaveConfig {
     pub max_distance: f32,
     pub spawn_count_scale: f32,
     pub spawn_cap: usize,
+    /// Special waves that spawn once when the player reaches a level, in order of level.
+    #[serde(default)]
+    pub boss_waves: Vec<BossWave>,
+}
+
+/// A wave of enemies that spawns all at once, ignoring the spawn cap.
+#[derive(Reflect, Serialize, Deserialize)]
+pub struct BossWave {
+    pub level: usize,
+    pub enemy: String,
+    pub count: usize,
 }
 
 impl Config for WaveConfig {
@@ -42,7 +57,12 @@ struct SpawnModifiers {
 
 #[derive(Component, Reflect, Default)]
 #[reflect(Component)]
-struct Wave(usize);
+struct Wave {
+    /// The number of beats counted.
+    beats: usize,
+    /// The number of boss waves spawned.
+    bosses: usize,
+}
 
 impl Configure for Wave {
     fn configure(app: &mut App) {
@@ -66,8 +86,11 @@ fn spawn_wave_enemies(
     camera_query: Query<&GlobalTransform>,
     enemy_query: Query<(), With<IsEnemy>>,
     mut wave_query: Query<(&mut Wave, &Selection)>,
-    level_query: Query<&Level>,
+    level_query: Query<(&Level, &PlayerKey)>,
     endless_mode: Res<EndlessMode>,
+    unlocks: Res<Unlocks>,
+    modifiers: Res<RunModifiers>,
+    mut rng: ResMut<RunRng>,
 ) {
     let config = r!(config.get());
     let actor_config = r!(actor_config.get());
@@ -79,28 +102,51 @@ fn spawn_wave_enemies(
         spawn_cap *= 2;
     }
 
-    let mut rng = rand::thread_rng();
     for (mut wave, selection) in &mut wave_query {
-        let level = c!(level_query.get(selection.0));
+        let (level, player_key) = c!(level_query.get(selection.0));
         let level = level.current;
 
-        wave.0 = wave.0.wrapping_add(1);
-        if wave.0 % config.spawn_cadence != 0 {
+        wave.beats = wave.beats.wrapping_add(1);
+        if wave.beats % config.spawn_cadence != 0 {
             return;
         }
 
+        if let Some(boss_wave) = config
+            .boss_waves
+            .get(wave.bosses)
+            .filter(|boss_wave| boss_wave.level <= level)
+        {
+            wave.bosses += 1;
+            for _ in 0..boss_wave.count {
+                let offset = Annulus::new(config.min_distance, config.max_distance)
+                    .sample_interior(&mut rng.0);
+                commands.spawn_with(enemy(&boss_wave.enemy)).insert((
+                    Transform::from_translation((center + offset).extend(0.0)),
+                    IsBoss,
+                ));
+            }
+            continue;
+        }
+
         let enemy_pool = actor_config
             .enemies
             .iter()
-            .filter(|(_, enemy)| enemy.min_level <= level && level <= enemy.max_level)
+            .filter(|(key, enemy)| {
+                **key != player_key.0
+                    && enemy.min_level <= level
+                    && level <= enemy.max_level
+                    && unlocks.has_actor(key, enemy)
+            })
             .collect::<Vec<_>>();
 
-        let spawn_count = (1 + (level as f32 * config.spawn_count_scale) as usize).min(spawn_cap);
+        let spawn_count = modifiers
+            .spawn_count(1 + (level as f32 * config.spawn_count_scale) as usize)
+            .min(spawn_cap);
         spawn_cap = spawn_cap.saturating_sub(spawn_count);
         for _ in 0..spawn_count {
-            let enemy_key = c!(enemy_pool.choose_weighted(&mut rng, |(_, enemy)| enemy.weight)).0;
+            let enemy_key = c!(enemy_pool.choose_weighted(&mut rng.0, |(_, enemy)| enemy.weight)).0;
             let offset =
-                Annulus::new(config.min_distance, config.max_distance).sample_interior(&mut rng);
+                Annulus::new(config.min_distance, config.max_distance).sample_interior(&mut rng.0);
             let spawn_point = center + offset;
 
             commands
@@ -115,3 +161,14 @@ pub fn wave(player: Entity) -> impl EntityCommand<World> {
         entity.insert((Name::new("Wave"), Wave::default(), Selection(player)));
     }
 }
+
+/// A marker component for enemies spawned in a boss wave.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct IsBoss;
+
+impl Configure for IsBoss {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+    }
+}
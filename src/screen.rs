@@ -1,3 +1,7 @@
+mod bestiary;
+mod calibration;
+mod challenge;
+mod compendium;
 mod intro;
 mod loading;
 pub mod playing;
@@ -21,6 +25,10 @@ pub fn plugin(app: &mut App) {
     app.add_plugins((
         splash::plugin,
         title::plugin,
+        calibration::plugin,
+        challenge::plugin,
+        compendium::plugin,
+        bestiary::plugin,
         intro::plugin,
         loading::plugin,
         playing::plugin,
@@ -34,6 +42,10 @@ pub enum Screen {
     #[default]
     Splash,
     Title,
+    Calibration,
+    Challenge,
+    Compendium,
+    Bestiary,
     Intro,
     Loading,
     Playing,
@@ -0,0 +1,333 @@
+use bevy::ecs::system::EntityCommand;
+use bevy::ecs::system::SystemState;
+use bevy::prelude::*;
+use bevy_mod_picking::prelude::*;
+use pyri_state::prelude::*;
+
+use crate::core::UpdateSet;
+use crate::game::actor::enemy::bestiary::Bestiary;
+use crate::game::actor::Actor;
+use crate::game::actor::ActorConfig;
+use crate::game::card::card_with_height;
+use crate::game::sprite::SpriteAnimation;
+use crate::screen::fade_in;
+use crate::screen::fade_out;
+use crate::screen::Screen;
+use crate::ui::prelude::*;
+use crate::util::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(StateFlush, Screen::Bestiary.on_enter(enter_bestiary));
+
+    app.configure::<(IsBestiaryGrid, BestiarySprite)>();
+}
+
+fn enter_bestiary(mut commands: Commands, ui_root: Res<UiRoot>) {
+    commands.spawn_with(fade_in);
+    commands
+        .spawn_with(bestiary_screen)
+        .set_parent(ui_root.body);
+}
+
+fn bestiary_screen(mut entity: EntityWorldMut) {
+    entity
+        .add(Style::COLUMN_MID.div())
+        .insert(Name::new("BestiaryScreen"))
+        .with_children(|children| {
+            children.spawn_with(header);
+            children.spawn_with(seen_count);
+            children.spawn_with(enemy_grid);
+            children.spawn_with(back_button);
+        });
+}
+
+const HEADER: &str = "bestiary.header";
+
+fn header(mut entity: EntityWorldMut) {
+    entity.insert((
+        Name::new("Header"),
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font: BOLD_FONT_HANDLE,
+                ..default()
+            },
+        )
+        .with_style(Style {
+            margin: UiRect::top(Vw(3.5)),
+            ..default()
+        }),
+        LocalizedText::new(HEADER),
+        DynamicFontSize::new(Vw(4.5)).with_step(8.0),
+        ThemeColorForText(vec![ThemeColor::BodyText]),
+    ));
+}
+
+fn seen_count(entity: Entity, world: &mut World) {
+    let (config, bestiary) =
+        SystemState::<(ConfigRef<ActorConfig>, Res<Bestiary>)>::new(world).get(world);
+    let (seen, total) = config
+        .get()
+        .map(|config| {
+            (
+                bestiary.count_seen(config.enemies.keys()),
+                config.enemies.len(),
+            )
+        })
+        .unwrap_or_default();
+
+    world.entity_mut(entity).insert((
+        Name::new("SeenCount"),
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font: FONT_HANDLE,
+                ..default()
+            },
+        )
+        .with_style(Style {
+            margin: UiRect::vertical(Vw(1.5)),
+            ..default()
+        }),
+        LocalizedText::new("bestiary.seen").with_args([seen, total]),
+        DynamicFontSize::new(Vw(2.0)).with_step(8.0),
+        ThemeColorForText(vec![ThemeColor::Indicator]),
+    ));
+}
+
+fn back_button(mut entity: EntityWorldMut) {
+    entity
+        .add(widget::menu_button_with_font_size("bestiary.back", Vw(2.5)))
+        .insert((
+            On::<Pointer<Click>>::run(|mut commands: Commands| {
+                commands.spawn_with(fade_out(Screen::Title));
+            }),
+            IsBackButton,
+            Style {
+                height: Vw(7.0),
+                width: Vw(16.0),
+                margin: UiRect::vertical(Vw(2.5)),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+        ));
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct IsBestiaryGrid;
+
+impl Configure for IsBestiaryGrid {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(Update, Screen::Bestiary.on_update(populate_bestiary_grid));
+    }
+}
+
+fn enemy_grid(mut entity: EntityWorldMut) {
+    entity.insert((
+        Name::new("EnemyGrid"),
+        NodeBundle {
+            style: Style {
+                width: Vw(90.0),
+                justify_content: JustifyContent::Center,
+                flex_wrap: FlexWrap::Wrap,
+                column_gap: Vw(2.0),
+                row_gap: Vw(2.0),
+                ..default()
+            },
+            ..default()
+        },
+        IsBestiaryGrid,
+    ));
+}
+
+/// Populate the grid once the enemies have loaded.
+fn populate_bestiary_grid(
+    mut commands: Commands,
+    config: ConfigRef<ActorConfig>,
+    grid_query: Query<Entity, (With<IsBestiaryGrid>, Without<Children>)>,
+) {
+    let config = rq!(config.get());
+    let mut enemies = config.enemies.iter().collect::<Vec<_>>();
+    enemies.sort_by_key(|(key, enemy)| (enemy.min_level, *key));
+
+    for entity in &grid_query {
+        commands.entity(entity).with_children(|children| {
+            for (key, _) in &enemies {
+                children.spawn_with(bestiary_entry(*key));
+            }
+        });
+    }
+}
+
+fn bestiary_entry(key: impl Into<String>) -> impl EntityCommand {
+    let key = key.into();
+
+    move |entity: Entity, world: &mut World| {
+        let (config, bestiary, locale) =
+            SystemState::<(ConfigRef<ActorConfig>, Res<Bestiary>, Locale)>::new(world).get(world);
+        let config = r!(config.get());
+        let enemy = r!(config.enemies.get(&key)).clone();
+        let name = enemy.localized_name(&key, &locale);
+        let seen = bestiary.is_seen(&key);
+
+        world
+            .entity_mut(entity)
+            .insert((
+                Name::new("BestiaryEntry"),
+                NodeBundle {
+                    style: Style {
+                        width: Vw(28.0),
+                        align_items: AlignItems::Center,
+                        column_gap: Vw(1.5),
+                        padding: UiRect::all(Vw(1.0)),
+                        border: UiRect::all(Vw(0.3)),
+                        ..default()
+                    },
+                    ..default()
+                },
+                ThemeColor::Popup.target::<BackgroundColor>(),
+                ThemeColor::BodyText.target::<BorderColor>(),
+            ))
+            .with_children(|children| {
+                let mut sprite = children.spawn((
+                    Name::new("Sprite"),
+                    ImageBundle {
+                        style: Style {
+                            height: Vw(6.0),
+                            ..default()
+                        },
+                        image: UiImage::new(enemy.texture.clone()),
+                        ..default()
+                    },
+                    TextureAtlas {
+                        layout: enemy.texture_atlas_layout.clone(),
+                        index: 0,
+                    },
+                    BestiarySprite(enemy.sprite_animation.clone()),
+                ));
+                if seen {
+                    children.spawn_with(enemy_details(name, enemy));
+                } else {
+                    sprite.insert(ThemeColor::PrimaryDisabled.target::<UiImage>());
+                    children.spawn(detail_text(
+                        LocalizedText::new("bestiary.unseen"),
+                        ThemeColor::PrimaryDisabled,
+                    ));
+                }
+            });
+    }
+}
+
+/// The name, stats and deck of an enemy that's been encountered.
+fn enemy_details(name: String, enemy: Actor) -> impl EntityCommand<World> {
+    move |mut entity: EntityWorldMut| {
+        let levels = if enemy.max_level == usize::MAX {
+            LocalizedText::new("bestiary.levels_from").with_args([enemy.min_level.max(1)])
+        } else {
+            LocalizedText::new("bestiary.levels")
+                .with_args([enemy.min_level.max(1), enemy.max_level])
+        };
+
+        entity
+            .insert((
+                Name::new("EnemyDetails"),
+                NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        row_gap: Vw(0.5),
+                        ..default()
+                    },
+                    ..default()
+                },
+            ))
+            .with_children(|children| {
+                children.spawn((
+                    Name::new("EnemyName"),
+                    TextBundle::from_section(
+                        name.clone(),
+                        TextStyle {
+                            font: BOLD_FONT_HANDLE,
+                            ..default()
+                        },
+                    ),
+                    DynamicFontSize::new(Vw(1.8)).with_step(8.0),
+                    ThemeColorForText(vec![ThemeColor::BodyText]),
+                ));
+                children.spawn(detail_text(
+                    LocalizedText::new("bestiary.stats")
+                        .with_args([enemy.health.max, enemy.xp_reward.0]),
+                    ThemeColor::BodyText,
+                ));
+                children.spawn(detail_text(levels, ThemeColor::Indicator));
+                children
+                    .spawn((
+                        Name::new("Deck"),
+                        NodeBundle {
+                            style: Style {
+                                flex_wrap: FlexWrap::Wrap,
+                                column_gap: Vw(0.5),
+                                row_gap: Vw(0.5),
+                                ..default()
+                            },
+                            ..default()
+                        },
+                    ))
+                    .with_children(|children| {
+                        for key in &enemy.deck.card_keys {
+                            children.spawn_with(card_with_height(key, None, Some(Vw(4.0))));
+                        }
+                    });
+            });
+    }
+}
+
+fn detail_text(text: LocalizedText, color: ThemeColor) -> impl Bundle {
+    (
+        Name::new("DetailText"),
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font: FONT_HANDLE,
+                ..default()
+            },
+        ),
+        text,
+        DynamicFontSize::new(Vw(1.4)).with_step(8.0),
+        ThemeColorForText(vec![color]),
+    )
+}
+
+/// The beats per minute to animate bestiary sprites at, since there's no music to follow here.
+const SPRITE_BPM: f32 = 120.0;
+
+/// An enemy sprite animated in the bestiary.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct BestiarySprite(SpriteAnimation);
+
+impl Configure for BestiarySprite {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(
+            Update,
+            Screen::Bestiary.on_update(animate_bestiary_sprites.in_set(UpdateSet::Update)),
+        );
+    }
+}
+
+fn animate_bestiary_sprites(
+    time: Res<Time>,
+    mut sprite_query: Query<(&BestiarySprite, &mut TextureAtlas)>,
+) {
+    // Sprite animations are measured in eighth-beats.
+    let beats = (time.elapsed_seconds() * SPRITE_BPM / 60.0 * 8.0) as usize;
+    for (sprite, mut atlas) in &mut sprite_query {
+        if sprite.0.total_beats == 0 {
+            continue;
+        }
+        atlas.index = sprite.0.index(beats);
+    }
+}
@@ -0,0 +1,300 @@
+use bevy::prelude::*;
+use bevy_kira_audio::prelude::*;
+use bevy_mod_picking::prelude::*;
+use pyri_state::prelude::*;
+
+use crate::core::settings::Settings;
+use crate::game::audio::mixer::SfxChannel;
+use crate::game::audio::AudioConfig;
+use crate::screen::fade_in;
+use crate::screen::fade_out;
+use crate::screen::playing::PlayingAssets;
+use crate::screen::Screen;
+use crate::ui::prelude::*;
+use crate::util::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(StateFlush, Screen::Calibration.on_enter(enter_calibration));
+
+    app.configure::<(Calibration, IsCalibrationStatus)>();
+}
+
+const HEADER: &str = "calibration.header";
+
+/// The number of taps needed to compute a latency offset.
+const REQUIRED_TAPS: usize = 16;
+
+fn enter_calibration(mut commands: Commands, ui_root: Res<UiRoot>) {
+    commands.spawn_with(fade_in);
+    commands
+        .spawn_with(calibration_screen)
+        .set_parent(ui_root.body);
+}
+
+fn calibration_screen(mut entity: EntityWorldMut) {
+    entity
+        .add(Style::COLUMN_MID.div())
+        .insert(Name::new("CalibrationScreen"))
+        .with_children(|children| {
+            children.spawn_with(header);
+            children.spawn_with(body);
+            children.spawn_with(button_container);
+        });
+}
+
+fn header(mut entity: EntityWorldMut) {
+    entity.insert((
+        Name::new("Header"),
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font: BOLD_FONT_HANDLE,
+                ..default()
+            },
+        )
+        .with_style(Style {
+            margin: UiRect::vertical(Vw(5.0)),
+            ..default()
+        }),
+        LocalizedText::new(HEADER),
+        DynamicFontSize::new(Vw(5.0)).with_step(8.0),
+        ThemeColorForText(vec![ThemeColor::BodyText]),
+    ));
+}
+
+fn body(mut entity: EntityWorldMut) {
+    entity
+        .insert((
+            Name::new("Body"),
+            NodeBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    row_gap: Vw(1.4),
+                    ..default()
+                },
+                ..default()
+            },
+        ))
+        .with_children(|children| {
+            children.spawn((
+                Name::new("Instructions"),
+                TextBundle::default(),
+                LocalizedText::rich("calibration.instructions"),
+                DynamicFontSize::new(Vw(3.5)).with_step(8.0),
+                ThemeColorForText(vec![
+                    ThemeColor::BodyText,
+                    ThemeColor::Indicator,
+                    ThemeColor::BodyText,
+                ]),
+            ));
+            children.spawn((
+                Name::new("Status"),
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font: FONT_HANDLE,
+                        ..default()
+                    },
+                ),
+                DynamicFontSize::new(Vw(3.5)).with_step(8.0),
+                ThemeColorForText(vec![ThemeColor::Indicator]),
+                LocalizedText::default(),
+                IsCalibrationStatus,
+            ));
+        });
+}
+
+fn button_container(mut entity: EntityWorldMut) {
+    entity
+        .insert((
+            Name::new("ButtonContainer"),
+            NodeBundle {
+                style: Style {
+                    align_items: AlignItems::Center,
+                    margin: UiRect::vertical(VMin(9.0)),
+                    column_gap: Vw(3.8),
+                    ..default()
+                },
+                ..default()
+            },
+        ))
+        .with_children(|children| {
+            children.spawn_with(save_button);
+            children.spawn_with(back_button);
+        });
+}
+
+fn save_button(mut entity: EntityWorldMut) {
+    entity
+        .add(widget::menu_button_with_font_size(
+            "calibration.save",
+            Vw(3.5),
+        ))
+        .insert((
+            On::<Pointer<Click>>::run(
+                |mut commands: Commands,
+                 calibration: Res<Calibration>,
+                 mut settings: ResMut<Settings>| {
+                    settings.audio_latency = rq!(calibration.latency());
+                    commands.spawn_with(fade_out(Screen::Title));
+                },
+            ),
+            Style {
+                height: Vw(9.0),
+                width: Vw(28.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+        ));
+}
+
+fn back_button(mut entity: EntityWorldMut) {
+    entity
+        .add(widget::menu_button_with_font_size(
+            "calibration.back",
+            Vw(3.5),
+        ))
+        .insert((
+            On::<Pointer<Click>>::run(|mut commands: Commands| {
+                commands.spawn_with(fade_out(Screen::Title));
+            }),
+            IsBackButton,
+            Style {
+                height: Vw(9.0),
+                width: Vw(28.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+        ));
+}
+
+/// The state of an in-progress latency calibration.
+#[derive(Resource, Reflect, Default)]
+#[reflect(Resource)]
+struct Calibration {
+    /// The real time (in seconds) that the click track started.
+    start: f64,
+    /// The number of clicks played so far.
+    clicks: usize,
+    /// The offset (in seconds) of each tap from its nearest click.
+    offsets: Vec<f64>,
+}
+
+impl Configure for Calibration {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.init_resource::<Self>();
+        app.add_systems(StateFlush, Screen::Calibration.on_enter(reset_calibration));
+        app.add_systems(
+            Update,
+            Screen::Calibration.on_update((play_click_track, record_taps).chain()),
+        );
+    }
+}
+
+impl Calibration {
+    /// The median tap offset, once there are enough taps.
+    fn latency(&self) -> Option<f64> {
+        if self.offsets.len() < REQUIRED_TAPS {
+            return None;
+        }
+
+        let mut offsets = self.offsets.clone();
+        offsets.sort_by(f64::total_cmp);
+        Some(offsets[offsets.len() / 2])
+    }
+}
+
+fn reset_calibration(time: Res<Time<Real>>, mut calibration: ResMut<Calibration>) {
+    *calibration = Calibration {
+        // Give the player a moment before the first click.
+        start: time.elapsed_seconds_f64() + 1.0,
+        ..default()
+    };
+}
+
+fn play_click_track(
+    time: Res<Time<Real>>,
+    config: ConfigRef<AudioConfig>,
+    audio: Res<AudioChannel<SfxChannel>>,
+    assets: Res<PlayingAssets>,
+    mut calibration: ResMut<Calibration>,
+) {
+    let config = r!(config.get());
+    let bpm = r!(config.tracks.first()).bpm;
+    let elapsed = time.elapsed_seconds_f64() - calibration.start;
+    if elapsed < 0.0 {
+        return;
+    }
+
+    let clicks = (elapsed * bpm / 60.0) as usize + 1;
+    if clicks > calibration.clicks {
+        calibration.clicks = clicks;
+        audio.play(assets.sfx_click.clone()).with_volume(0.8);
+    }
+}
+
+fn record_taps(
+    time: Res<Time<Real>>,
+    config: ConfigRef<AudioConfig>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepad_input: Res<ButtonInput<GamepadButton>>,
+    mut calibration: ResMut<Calibration>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::Space)
+        && !gamepad_input
+            .get_just_pressed()
+            .any(|button| button.button_type == GamepadButtonType::South)
+    {
+        return;
+    }
+
+    let config = r!(config.get());
+    let secs_per_beat = 60.0 / r!(config.tracks.first()).bpm;
+    let elapsed = time.elapsed_seconds_f64() - calibration.start;
+    let offset = elapsed - (elapsed / secs_per_beat).round() * secs_per_beat;
+
+    // Ignore taps that are too far off to be following the clicks.
+    if offset.abs() < secs_per_beat / 3.0 {
+        calibration.offsets.push(offset);
+    }
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct IsCalibrationStatus;
+
+impl Configure for IsCalibrationStatus {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(
+            Update,
+            Screen::Calibration
+                .on_update(update_calibration_status.run_if(resource_changed::<Calibration>)),
+        );
+    }
+}
+
+fn update_calibration_status(
+    calibration: Res<Calibration>,
+    settings: Res<Settings>,
+    mut status_query: Query<&mut LocalizedText, With<IsCalibrationStatus>>,
+) {
+    let status = match calibration.latency() {
+        Some(latency) => LocalizedText::new("calibration.latency")
+            .with_args([format!("{:+.0}", latency * 1000.0)]),
+        None => LocalizedText::new("calibration.taps").with_args([
+            calibration.offsets.len().to_string(),
+            REQUIRED_TAPS.to_string(),
+            format!("{:+.0}", settings.audio_latency * 1000.0),
+        ]),
+    };
+
+    for mut text in &mut status_query {
+        text.key.clone_from(&status.key);
+        text.args.clone_from(&status.args);
+    }
+}
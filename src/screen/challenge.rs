@@ -0,0 +1,242 @@
+use bevy::ecs::system::EntityCommand;
+use bevy::ecs::system::SystemState;
+use bevy::prelude::*;
+use bevy_mod_picking::prelude::*;
+use pyri_state::prelude::*;
+
+use crate::game::challenge::ActiveChallenge;
+use crate::game::challenge::Challenge;
+use crate::game::challenge::ChallengeConfig;
+use crate::game::challenge::ChallengeKind;
+use crate::game::challenge::ChallengeRecords;
+use crate::screen::fade_in;
+use crate::screen::fade_out;
+use crate::screen::Screen;
+use crate::ui::prelude::*;
+use crate::util::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(StateFlush, Screen::Challenge.on_enter(enter_challenge));
+
+    app.configure::<IsChallengeRow>();
+}
+
+fn enter_challenge(mut commands: Commands, ui_root: Res<UiRoot>) {
+    commands.spawn_with(fade_in);
+    commands
+        .spawn_with(challenge_screen)
+        .set_parent(ui_root.body);
+}
+
+fn challenge_screen(mut entity: EntityWorldMut) {
+    entity
+        .add(Style::COLUMN_MID.div())
+        .insert(Name::new("ChallengeScreen"))
+        .with_children(|children| {
+            children.spawn_with(header);
+            children.spawn_with(challenge_row);
+            children.spawn_with(back_button);
+        });
+}
+
+const HEADER: &str = "challenge.header";
+
+fn header(mut entity: EntityWorldMut) {
+    entity.insert((
+        Name::new("Header"),
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font: BOLD_FONT_HANDLE,
+                ..default()
+            },
+        )
+        .with_style(Style {
+            margin: UiRect::vertical(Vw(3.5)),
+            ..default()
+        }),
+        LocalizedText::new(HEADER),
+        DynamicFontSize::new(Vw(4.5)).with_step(8.0),
+        ThemeColorForText(vec![ThemeColor::BodyText]),
+    ));
+}
+
+fn back_button(mut entity: EntityWorldMut) {
+    entity
+        .add(widget::menu_button_with_font_size(
+            "challenge.back",
+            Vw(2.5),
+        ))
+        .insert((
+            On::<Pointer<Click>>::run(|mut commands: Commands| {
+                commands.spawn_with(fade_out(Screen::Title));
+            }),
+            IsBackButton,
+            Style {
+                height: Vw(7.0),
+                width: Vw(16.0),
+                margin: UiRect::vertical(Vw(3.0)),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+        ));
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct IsChallengeRow;
+
+impl Configure for IsChallengeRow {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(Update, Screen::Challenge.on_update(populate_challenge_row));
+    }
+}
+
+fn challenge_row(mut entity: EntityWorldMut) {
+    entity.insert((
+        Name::new("ChallengeRow"),
+        NodeBundle {
+            style: Style {
+                column_gap: Vw(4.0),
+                ..default()
+            },
+            ..default()
+        },
+        IsChallengeRow,
+    ));
+}
+
+/// Populate the row once the challenge modifiers have loaded.
+fn populate_challenge_row(
+    mut commands: Commands,
+    config: ConfigRef<ChallengeConfig>,
+    row_query: Query<Entity, (With<IsChallengeRow>, Without<Children>)>,
+) {
+    let config = rq!(config.get());
+    for entity in &row_query {
+        commands.entity(entity).with_children(|children| {
+            for kind in [ChallengeKind::Daily, ChallengeKind::Weekly] {
+                children.spawn_with(challenge_panel(Challenge::current(kind, config)));
+            }
+        });
+    }
+}
+
+fn challenge_panel(challenge: Challenge) -> impl EntityCommand {
+    move |entity: Entity, world: &mut World| {
+        let (config, records, locale) =
+            SystemState::<(ConfigRef<ChallengeConfig>, Res<ChallengeRecords>, Locale)>::new(world)
+                .get(world);
+        let config = r!(config.get());
+        let modifiers = challenge
+            .modifiers
+            .iter()
+            .filter_map(|key| {
+                let modifier = config.modifiers.get(key)?;
+                Some(LocalizedText::rich("challenge.modifier").with_args([
+                    modifier.localized_name(key, &locale),
+                    modifier.localized_description(key, &locale),
+                ]))
+            })
+            .collect::<Vec<_>>();
+        let best = match records.best.get(&challenge.id()) {
+            Some(best) => LocalizedText::new("challenge.best").with_args([best]),
+            None => LocalizedText::new("challenge.no_best"),
+        };
+        let (title, date) = match challenge.kind {
+            ChallengeKind::Daily => ("challenge.daily", "challenge.day_of"),
+            ChallengeKind::Weekly => ("challenge.weekly", "challenge.week_of"),
+        };
+        let start_date = challenge
+            .start_date()
+            .map(|x| x.to_string())
+            .unwrap_or_default();
+        let date = LocalizedText::new(date).with_args([start_date]);
+
+        world
+            .entity_mut(entity)
+            .insert((
+                Name::new("ChallengePanel"),
+                NodeBundle {
+                    style: Style {
+                        width: Vw(38.0),
+                        align_items: AlignItems::Center,
+                        flex_direction: FlexDirection::Column,
+                        row_gap: Vw(1.0),
+                        padding: UiRect::all(Vw(1.5)),
+                        border: UiRect::all(Vw(0.4)),
+                        ..default()
+                    },
+                    ..default()
+                },
+                ThemeColor::Popup.target::<BackgroundColor>(),
+                ThemeColor::BodyText.target::<BorderColor>(),
+            ))
+            .with_children(|children| {
+                children.spawn((
+                    Name::new("Title"),
+                    TextBundle::from_section(
+                        "",
+                        TextStyle {
+                            font: BOLD_FONT_HANDLE,
+                            ..default()
+                        },
+                    ),
+                    LocalizedText::new(title),
+                    DynamicFontSize::new(Vw(3.0)).with_step(8.0),
+                    ThemeColorForText(vec![ThemeColor::BodyText]),
+                ));
+                children.spawn(panel_text(date, ThemeColor::Indicator));
+                for modifier in modifiers {
+                    children.spawn(panel_text(modifier, ThemeColor::BodyText));
+                }
+                children.spawn(panel_text(best, ThemeColor::Indicator));
+                children.spawn_with(play_button(challenge));
+            });
+    }
+}
+
+fn panel_text(text: LocalizedText, color: ThemeColor) -> impl Bundle {
+    (
+        Name::new("PanelText"),
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font: FONT_HANDLE,
+                ..default()
+            },
+        )
+        .with_text_justify(JustifyText::Center),
+        text,
+        DynamicFontSize::new(Vw(1.8)).with_step(8.0),
+        ThemeColorForText(vec![color]),
+    )
+}
+
+fn play_button(challenge: Challenge) -> impl EntityCommand<World> {
+    move |mut entity: EntityWorldMut| {
+        entity
+            .add(widget::menu_button_with_font_size(
+                "challenge.play",
+                Vw(2.5),
+            ))
+            .insert((
+                On::<Pointer<Click>>::run(
+                    move |mut commands: Commands, mut active: ResMut<ActiveChallenge>| {
+                        active.0 = Some(challenge.clone());
+                        commands.spawn_with(fade_out(Screen::Intro));
+                    },
+                ),
+                Style {
+                    height: Vw(7.0),
+                    width: Vw(20.0),
+                    margin: UiRect::top(Vw(1.0)),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+            ));
+    }
+}
@@ -0,0 +1,424 @@
+use bevy::ecs::system::EntityCommand;
+use bevy::ecs::system::SystemState;
+use bevy::prelude::*;
+use bevy_mod_picking::prelude::*;
+use pyri_state::prelude::*;
+
+use crate::game::card::action::CardActionKey;
+use crate::game::card::card_with_height;
+use crate::game::card::compendium::Compendium;
+use crate::game::card::Card;
+use crate::game::card::CardConfig;
+use crate::screen::fade_in;
+use crate::screen::fade_out;
+use crate::screen::Screen;
+use crate::ui::prelude::*;
+use crate::util::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(StateFlush, Screen::Compendium.on_enter(enter_compendium));
+
+    app.configure::<(CompendiumFilter, IsCompendiumGrid)>();
+}
+
+fn enter_compendium(mut commands: Commands, ui_root: Res<UiRoot>) {
+    commands.spawn_with(fade_in);
+    commands
+        .spawn_with(compendium_screen)
+        .set_parent(ui_root.body);
+}
+
+fn compendium_screen(mut entity: EntityWorldMut) {
+    entity
+        .add(Style::COLUMN_MID.div())
+        .insert(Name::new("CompendiumScreen"))
+        .with_children(|children| {
+            children.spawn_with(header);
+            children.spawn_with(seen_count);
+            children.spawn_with(filter_row);
+            children.spawn_with(card_grid);
+        });
+}
+
+const HEADER: &str = "compendium.header";
+
+fn header(mut entity: EntityWorldMut) {
+    entity.insert((
+        Name::new("Header"),
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font: BOLD_FONT_HANDLE,
+                ..default()
+            },
+        )
+        .with_style(Style {
+            margin: UiRect::top(Vw(3.5)),
+            ..default()
+        }),
+        LocalizedText::new(HEADER),
+        DynamicFontSize::new(Vw(4.5)).with_step(8.0),
+        ThemeColorForText(vec![ThemeColor::BodyText]),
+    ));
+}
+
+fn seen_count(entity: Entity, world: &mut World) {
+    let (config, compendium) =
+        SystemState::<(ConfigRef<CardConfig>, Res<Compendium>)>::new(world).get(world);
+    let (seen, total) = config
+        .get()
+        .map(|config| {
+            (
+                compendium.count_seen(config.card_map.keys()),
+                config.card_map.len(),
+            )
+        })
+        .unwrap_or_default();
+
+    world.entity_mut(entity).insert((
+        Name::new("SeenCount"),
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font: FONT_HANDLE,
+                ..default()
+            },
+        )
+        .with_style(Style {
+            margin: UiRect::vertical(Vw(1.5)),
+            ..default()
+        }),
+        LocalizedText::new("compendium.seen").with_args([seen, total]),
+        DynamicFontSize::new(Vw(2.0)).with_step(8.0),
+        ThemeColorForText(vec![ThemeColor::Indicator]),
+    ));
+}
+
+fn filter_row(mut entity: EntityWorldMut) {
+    entity
+        .insert((
+            Name::new("FilterRow"),
+            NodeBundle {
+                style: Style {
+                    column_gap: Vw(2.0),
+                    margin: UiRect::bottom(Vw(2.5)),
+                    ..default()
+                },
+                ..default()
+            },
+        ))
+        .with_children(|children| {
+            children.spawn_with(category_button);
+            children.spawn_with(level_button);
+            children.spawn_with(back_button);
+        });
+}
+
+fn category_button(mut entity: EntityWorldMut) {
+    let label = entity
+        .world()
+        .resource::<CompendiumFilter>()
+        .category_label();
+    entity
+        .add(widget::menu_button_with_font_size(label, Vw(2.5)))
+        .insert((
+            On::<Pointer<Click>>::run(
+                |event: Listener<Pointer<Click>>,
+                 mut filter: ResMut<CompendiumFilter>,
+                 children_query: Query<&Children>,
+                 mut text_query: Query<&mut LocalizedText>| {
+                    filter.cycle_category();
+                    for &child in r!(children_query.get(event.listener())) {
+                        *cq!(text_query.get_mut(child)) = filter.category_label();
+                    }
+                },
+            ),
+            Style {
+                height: Vw(7.0),
+                width: Vw(24.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+        ));
+}
+
+fn level_button(mut entity: EntityWorldMut) {
+    let label = entity.world().resource::<CompendiumFilter>().level_label();
+    entity
+        .add(widget::menu_button_with_font_size(label, Vw(2.5)))
+        .insert((
+            On::<Pointer<Click>>::run(
+                |event: Listener<Pointer<Click>>,
+                 mut filter: ResMut<CompendiumFilter>,
+                 children_query: Query<&Children>,
+                 mut text_query: Query<&mut LocalizedText>| {
+                    filter.cycle_level_range();
+                    for &child in r!(children_query.get(event.listener())) {
+                        *cq!(text_query.get_mut(child)) = filter.level_label();
+                    }
+                },
+            ),
+            Style {
+                height: Vw(7.0),
+                width: Vw(24.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+        ));
+}
+
+fn back_button(mut entity: EntityWorldMut) {
+    entity
+        .add(widget::menu_button_with_font_size(
+            "compendium.back",
+            Vw(2.5),
+        ))
+        .insert((
+            On::<Pointer<Click>>::run(|mut commands: Commands| {
+                commands.spawn_with(fade_out(Screen::Title));
+            }),
+            IsBackButton,
+            Style {
+                height: Vw(7.0),
+                width: Vw(16.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+        ));
+}
+
+/// The level ranges to filter by, inclusive.
+const LEVEL_RANGES: [(usize, usize); 4] = [(1, 3), (4, 6), (7, 9), (10, usize::MAX)];
+
+/// Which cards to list in the compendium.
+#[derive(Resource, Reflect, Default)]
+#[reflect(Resource)]
+struct CompendiumFilter {
+    category: Option<CardActionKey>,
+    /// An index into [`LEVEL_RANGES`].
+    level_range: Option<usize>,
+}
+
+impl Configure for CompendiumFilter {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.init_resource::<Self>();
+    }
+}
+
+impl CompendiumFilter {
+    fn matches(&self, card: &Card) -> bool {
+        let category = self.category.map_or(true, |x| x == card.action_key);
+        let level = self
+            .level_range
+            .map(|i| LEVEL_RANGES[i])
+            .map_or(true, |(lo, hi)| {
+                card.min_level <= hi && lo <= card.max_level
+            });
+        category && level
+    }
+
+    fn cycle_category(&mut self) {
+        let next = match self.category {
+            None => 0,
+            Some(key) => {
+                CardActionKey::ALL
+                    .iter()
+                    .position(|&x| x == key)
+                    .unwrap_or(0)
+                    + 1
+            },
+        };
+        self.category = CardActionKey::ALL.get(next).copied();
+    }
+
+    fn cycle_level_range(&mut self) {
+        let next = self.level_range.map_or(0, |i| i + 1);
+        self.level_range = (next < LEVEL_RANGES.len()).then_some(next);
+    }
+
+    fn category_label(&self) -> LocalizedText {
+        LocalizedText::new(
+            self.category
+                .map_or("compendium.all_categories", |x| x.category_key()),
+        )
+    }
+
+    fn level_label(&self) -> LocalizedText {
+        match self.level_range.map(|i| LEVEL_RANGES[i]) {
+            None => LocalizedText::new("compendium.all_levels"),
+            Some((lo, usize::MAX)) => LocalizedText::new("compendium.levels_from").with_args([lo]),
+            Some((lo, hi)) => LocalizedText::new("compendium.levels").with_args([lo, hi]),
+        }
+    }
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct IsCompendiumGrid;
+
+impl Configure for IsCompendiumGrid {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(
+            Update,
+            Screen::Compendium.on_update(populate_compendium_grid),
+        );
+    }
+}
+
+fn card_grid(mut entity: EntityWorldMut) {
+    entity.insert((
+        Name::new("CardGrid"),
+        NodeBundle {
+            style: Style {
+                width: Vw(90.0),
+                justify_content: JustifyContent::Center,
+                flex_wrap: FlexWrap::Wrap,
+                column_gap: Vw(1.0),
+                row_gap: Vw(1.5),
+                ..default()
+            },
+            ..default()
+        },
+        IsCompendiumGrid,
+    ));
+}
+
+/// Repopulate the grid when the filter changes (or when it's still empty, in case the cards were loading).
+fn populate_compendium_grid(
+    mut commands: Commands,
+    config: ConfigRef<CardConfig>,
+    filter: Res<CompendiumFilter>,
+    grid_query: Query<(Entity, Option<&Children>), With<IsCompendiumGrid>>,
+) {
+    let config = rq!(config.get());
+    let mut cards = config
+        .card_map
+        .iter()
+        .filter(|(_, card)| filter.matches(card))
+        .collect::<Vec<_>>();
+    cards.sort_by_key(|(key, card)| {
+        let category = CardActionKey::ALL
+            .iter()
+            .position(|&x| x == card.action_key);
+        (category, card.min_level, *key)
+    });
+
+    for (entity, children) in &grid_query {
+        if !filter.is_changed() && children.is_some() {
+            continue;
+        }
+
+        commands
+            .entity(entity)
+            .despawn_descendants()
+            .with_children(|children| {
+                for (key, _) in &cards {
+                    children.spawn_with(compendium_entry(*key));
+                }
+            });
+    }
+}
+
+fn compendium_entry(key: impl Into<String>) -> impl EntityCommand {
+    let key = key.into();
+
+    move |entity: Entity, world: &mut World| {
+        let (config, compendium, locale) =
+            SystemState::<(ConfigRef<CardConfig>, Res<Compendium>, Locale)>::new(world).get(world);
+        let config = r!(config.get());
+        let card = r!(config.card_map.get(&key));
+        let seen = compendium.is_seen(&key);
+        let name = card.localized_name(&key, &locale);
+        let height = Vw(5.5);
+
+        world
+            .entity_mut(entity)
+            .insert((
+                Name::new("CompendiumEntry"),
+                NodeBundle {
+                    style: Style {
+                        width: Vw(9.5),
+                        align_items: AlignItems::Center,
+                        flex_direction: FlexDirection::Column,
+                        row_gap: Vw(0.5),
+                        ..default()
+                    },
+                    ..default()
+                },
+            ))
+            .with_children(|children| {
+                if seen {
+                    children.spawn_with(card_with_height(&key, None, Some(height)));
+                    children.spawn((
+                        Name::new("CardName"),
+                        TextBundle::from_section(
+                            name,
+                            TextStyle {
+                                font: FONT_HANDLE,
+                                ..default()
+                            },
+                        )
+                        .with_text_justify(JustifyText::Center),
+                        DynamicFontSize::new(Vw(1.4)).with_step(8.0),
+                        ThemeColorForText(vec![ThemeColor::BodyText]),
+                    ));
+                } else {
+                    children.spawn_with(unseen_card(height));
+                    children.spawn((
+                        Name::new("CardName"),
+                        TextBundle::from_section(
+                            "",
+                            TextStyle {
+                                font: FONT_HANDLE,
+                                ..default()
+                            },
+                        ),
+                        LocalizedText::new("compendium.unseen"),
+                        DynamicFontSize::new(Vw(1.4)).with_step(8.0),
+                        ThemeColorForText(vec![ThemeColor::PrimaryDisabled]),
+                    ));
+                }
+            });
+    }
+}
+
+/// A blank card for a card that hasn't been seen yet.
+fn unseen_card(height: Val) -> impl EntityCommand<World> {
+    move |mut entity: EntityWorldMut| {
+        entity
+            .insert((
+                Name::new("UnseenCard"),
+                NodeBundle {
+                    style: Style {
+                        height,
+                        aspect_ratio: Some(0.75),
+                        border: UiRect::all(height / 18.0),
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::Center,
+                        ..default()
+                    },
+                    ..default()
+                },
+                ThemeColor::PrimaryDisabled.target::<BorderColor>(),
+            ))
+            .with_children(|children| {
+                children.spawn((
+                    Name::new("Text"),
+                    TextBundle::from_section(
+                        "?",
+                        TextStyle {
+                            font: BOLD_FONT_HANDLE,
+                            ..default()
+                        },
+                    ),
+                    DynamicFontSize::new(Vw(2.5)).with_step(8.0),
+                    ThemeColorForText(vec![ThemeColor::PrimaryDisabled]),
+                ));
+            });
+    }
+}
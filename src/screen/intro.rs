@@ -4,15 +4,23 @@ use bevy_mod_picking::prelude::*;
 use iyes_progress::prelude::*;
 use pyri_state::prelude::*;
 
+use crate::core::locale::Locale;
+use crate::core::settings::Settings;
 use crate::game::actor::health::HealthConfig;
 use crate::game::actor::level::LevelConfig;
 use crate::game::actor::ActorConfig;
 use crate::game::audio::AudioConfig;
+use crate::game::card::perform::PerformerMode;
 use crate::game::card::CardConfig;
+use crate::game::combat::number::NumberConfig;
 use crate::game::combat::projectile::ProjectileConfig;
+use crate::game::ground::GroundConfig;
+use crate::game::unlock::Unlocks;
+use crate::game::vfx::VfxConfig;
 use crate::game::wave::WaveConfig;
 use crate::screen::fade_in;
 use crate::screen::fade_out;
+use crate::screen::playing::hud::HudConfig;
 use crate::screen::playing::PlayingAssets;
 use crate::screen::Screen;
 use crate::ui::prelude::*;
@@ -30,16 +38,22 @@ pub(super) fn plugin(app: &mut App) {
         Screen::Intro.on_update((
             ActorConfig::progress.track_progress(),
             CardConfig::progress.track_progress(),
+            GroundConfig::progress.track_progress(),
             HealthConfig::progress.track_progress(),
+            HudConfig::progress.track_progress(),
             LevelConfig::progress.track_progress(),
+            NumberConfig::progress.track_progress(),
             AudioConfig::progress.track_progress(),
             ProjectileConfig::progress.track_progress(),
+            VfxConfig::progress.track_progress(),
             WaveConfig::progress.track_progress(),
         )),
     );
+
+    app.configure::<(IsPerformerButton, IsCharacterButton)>();
 }
 
-const HEADER: &str = "How to play:";
+const HEADER: &str = "intro.header";
 
 fn enter_intro(mut commands: Commands, ui_root: Res<UiRoot>) {
     commands.spawn_with(fade_in);
@@ -61,7 +75,7 @@ fn header(mut entity: EntityWorldMut) {
     entity.insert((
         Name::new("Header"),
         TextBundle::from_section(
-            HEADER,
+            "",
             TextStyle {
                 font: BOLD_FONT_HANDLE,
                 ..default()
@@ -71,6 +85,7 @@ fn header(mut entity: EntityWorldMut) {
             margin: UiRect::vertical(Vw(5.0)),
             ..default()
         }),
+        LocalizedText::new(HEADER),
         DynamicFontSize::new(Vw(5.0)).with_step(8.0),
         ThemeColorForText(vec![ThemeColor::BodyText]),
     ));
@@ -91,18 +106,14 @@ fn body(mut entity: EntityWorldMut) {
             },
         ))
         .with_children(|children| {
-            for (i, text) in [
-                "Cards are played to the rhythm,",
-                "using your mouse to aim.",
-                "Show off your dance moves with 'em,",
-                "reach [b]Level 10[r] for fame!",
-            ]
-            .into_iter()
-            .enumerate()
+            for (i, key) in ["intro.line0", "intro.line1", "intro.line2", "intro.line3"]
+                .into_iter()
+                .enumerate()
             {
                 children.spawn((
                     Name::new(format!("Span{}", i)),
-                    TextBundle::from_sections(parse_rich(text)),
+                    TextBundle::default(),
+                    LocalizedText::rich(key),
                     DynamicFontSize::new(Vw(3.5)).with_step(8.0),
                     ThemeColorForText(vec![
                         ThemeColor::BodyText,
@@ -132,12 +143,14 @@ fn button_container(mut entity: EntityWorldMut) {
         ))
         .with_children(|children| {
             children.spawn_with(play_button);
+            children.spawn_with(character_button);
+            children.spawn_with(performer_button);
         });
 }
 
 fn play_button(mut entity: EntityWorldMut) {
     entity
-        .add(widget::menu_button("Let's dance!"))
+        .add(widget::menu_button("intro.play"))
         .insert(On::<Pointer<Click>>::run(
             |mut commands: Commands, progress: Res<ProgressCounter>| {
                 let Progress { done, total } = progress.progress_complete();
@@ -149,3 +162,131 @@ fn play_button(mut entity: EntityWorldMut) {
             },
         ));
 }
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct IsPerformerButton;
+
+impl Configure for IsPerformerButton {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(
+            Update,
+            Screen::Intro
+                .on_update(update_performer_button_text.run_if(resource_changed::<PerformerMode>)),
+        );
+    }
+}
+
+fn performer_button_text(performer_mode: bool) -> &'static str {
+    if performer_mode {
+        "intro.performer_on"
+    } else {
+        "intro.performer_off"
+    }
+}
+
+fn performer_button(mut entity: EntityWorldMut) {
+    let text = performer_button_text(entity.world().resource::<PerformerMode>().0);
+    entity
+        .add(widget::menu_button_with_font_size(text, Vw(3.0)))
+        .insert((
+            IsPerformerButton,
+            On::<Pointer<Click>>::run(|mut performer_mode: ResMut<PerformerMode>| {
+                performer_mode.0 = !performer_mode.0;
+            }),
+            Style {
+                height: Vw(8.0),
+                width: Vw(32.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+        ));
+}
+
+fn update_performer_button_text(
+    performer_mode: Res<PerformerMode>,
+    button_query: Query<&Children, With<IsPerformerButton>>,
+    mut text_query: Query<&mut LocalizedText>,
+) {
+    for children in &button_query {
+        for &child in children {
+            let mut text = c!(text_query.get_mut(child));
+            text.key = performer_button_text(performer_mode.0).to_string();
+        }
+    }
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct IsCharacterButton;
+
+impl Configure for IsCharacterButton {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        // The actor config may still be loading, so this can't only run when the settings change.
+        app.add_systems(
+            Update,
+            Screen::Intro.on_update(update_character_button_text),
+        );
+    }
+}
+
+fn character_button(mut entity: EntityWorldMut) {
+    entity
+        .add(widget::menu_button_with_font_size(
+            "intro.character",
+            Vw(3.0),
+        ))
+        .insert((
+            IsCharacterButton,
+            On::<Pointer<Click>>::run(cycle_character),
+            Style {
+                height: Vw(8.0),
+                width: Vw(32.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+        ));
+}
+
+/// Choose the next unlocked player character.
+fn cycle_character(
+    mut settings: ResMut<Settings>,
+    actor_config: ConfigRef<ActorConfig>,
+    unlocks: Res<Unlocks>,
+) {
+    let config = r!(actor_config.get());
+    let keys = config.unlocked_players(&unlocks);
+    let current = r!(config.player_key(&settings.player, &unlocks));
+    let i = keys
+        .iter()
+        .position(|&key| key == current)
+        .unwrap_or_default();
+    let next = keys[(i + 1) % keys.len()].to_string();
+    settings.player = next;
+}
+
+fn update_character_button_text(
+    settings: Res<Settings>,
+    actor_config: ConfigRef<ActorConfig>,
+    unlocks: Res<Unlocks>,
+    locale: Locale,
+    button_query: Query<&Children, With<IsCharacterButton>>,
+    mut text_query: Query<&mut LocalizedText>,
+) {
+    let config = rq!(actor_config.get());
+    let key = r!(config.player_key(&settings.player, &unlocks));
+    let name = r!(config.players.get(key)).localized_name(key, &locale);
+
+    for children in &button_query {
+        for &child in children {
+            let mut text = c!(text_query.get_mut(child));
+            if text.args.first() != Some(&name) {
+                text.args = vec![name.clone()];
+            }
+        }
+    }
+}
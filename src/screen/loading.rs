@@ -4,15 +4,21 @@ use bevy_asset_loader::prelude::*;
 use iyes_progress::prelude::*;
 use pyri_state::prelude::*;
 
+use crate::game::achievement::AchievementConfig;
 use crate::game::actor::health::HealthConfig;
 use crate::game::actor::level::LevelConfig;
 use crate::game::actor::ActorConfig;
 use crate::game::audio::AudioConfig;
 use crate::game::card::CardConfig;
+use crate::game::challenge::ChallengeConfig;
 use crate::game::combat::projectile::ProjectileConfig;
+use crate::game::ground::GroundConfig;
+use crate::game::score::ScoreConfig;
+use crate::game::vfx::VfxConfig;
 use crate::game::wave::WaveConfig;
 use crate::screen::fade_in;
 use crate::screen::fade_out;
+use crate::screen::playing::hud::HudConfig;
 use crate::screen::playing::PlayingAssets;
 use crate::screen::Screen;
 use crate::ui::prelude::*;
@@ -28,12 +34,18 @@ pub(super) fn plugin(app: &mut App) {
         Update,
         // TODO: This is kinda silly. Find a better way later.
         Screen::Loading.on_update((
+            AchievementConfig::progress.track_progress(),
             ActorConfig::progress.track_progress(),
             CardConfig::progress.track_progress(),
+            ChallengeConfig::progress.track_progress(),
+            GroundConfig::progress.track_progress(),
             HealthConfig::progress.track_progress(),
+            HudConfig::progress.track_progress(),
             LevelConfig::progress.track_progress(),
             AudioConfig::progress.track_progress(),
             ProjectileConfig::progress.track_progress(),
+            ScoreConfig::progress.track_progress(),
+            VfxConfig::progress.track_progress(),
             WaveConfig::progress.track_progress(),
         )),
     );
@@ -105,7 +117,7 @@ fn loading_text(mut entity: EntityWorldMut) {
                 ..default()
             },
             text: Text::from_section(
-                "Loading...",
+                "",
                 TextStyle {
                     font: THICK_FONT_HANDLE,
                     ..default()
@@ -113,6 +125,7 @@ fn loading_text(mut entity: EntityWorldMut) {
             ),
             ..default()
         },
+        LocalizedText::new("loading.header"),
         DynamicFontSize::new(Vw(5.0)).with_step(8.0),
         ThemeColorForText(vec![ThemeColor::BodyText]),
     ));
@@ -1,6 +1,9 @@
+pub mod deck_menu;
 pub mod defeat_menu;
 pub mod hud;
+pub mod leaderboard;
 pub mod level_up_menu;
+pub mod minimap;
 pub mod pause_menu;
 pub mod victory_menu;
 
@@ -13,31 +16,43 @@ use pyri_state::prelude::*;
 use pyri_state::schedule::ResolveStateSet;
 
 use crate::core::pause::Pause;
+use crate::core::settings::Settings;
 use crate::game::actor::player::player;
+use crate::game::actor::ActorConfig;
 use crate::game::audio::music::start_music;
 use crate::game::audio::music::stop_music;
 use crate::game::audio::music::Beat;
+use crate::game::challenge::prepare_run;
 use crate::game::ground::ground;
 use crate::game::spotlight::spotlight_lamp_spawner;
 use crate::game::stats::Stats;
+use crate::game::unlock::Unlocks;
 use crate::game::wave::wave;
 use crate::game::GameRoot;
 use crate::screen::fade_in;
 use crate::screen::playing::hud::playing_hud;
 use crate::screen::Screen;
+use crate::ui::focus::FocusAction;
 use crate::ui::prelude::*;
 use crate::util::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(
         StateFlush,
-        Screen::Playing.on_edge(stop_music, (enter_playing, start_music)),
+        Screen::Playing.on_edge(
+            stop_music,
+            (prepare_run, enter_playing, start_music).chain(),
+        ),
     );
 
     app.configure::<(PlayingAssets, PlayingAction, PlayingMenu)>();
 
     app.add_plugins((
+        deck_menu::plugin,
+        hud::plugin,
+        leaderboard::plugin,
         level_up_menu::plugin,
+        minimap::plugin,
         pause_menu::plugin,
         victory_menu::plugin,
         defeat_menu::plugin,
@@ -50,6 +65,9 @@ fn enter_playing(
     ui_root: Res<UiRoot>,
     mut stats: ResMut<Stats>,
     mut beat: ResMut<Beat>,
+    settings: Res<Settings>,
+    actor_config: ConfigRef<ActorConfig>,
+    unlocks: Res<Unlocks>,
 ) {
     commands.spawn_with(fade_in);
 
@@ -57,9 +75,10 @@ fn enter_playing(
     *stats = default();
     *beat = default();
 
-    // TODO: Character select screen.
     // Spawn player.
-    let player = commands.spawn_with(player("pink")).id();
+    let actor_config = r!(actor_config.get());
+    let player_key = r!(actor_config.player_key(&settings.player, &unlocks));
+    let player = commands.spawn_with(player(player_key)).id();
 
     // Spawn enemies.
     commands
@@ -100,8 +119,6 @@ pub struct PlayingAssets {
     #[asset(path = "image/vfx/bubble.png")]
     pub bubble: Handle<Image>,
 
-    #[asset(path = "audio/music/Menu Theme.ogg")]
-    pub music: Handle<AudioSource>,
     #[asset(path = "audio/sfx/Restart_1.ogg")]
     pub sfx_restart: Handle<AudioSource>,
     #[asset(path = "audio/sfx/Level Up_1.ogg")]
@@ -118,6 +135,8 @@ pub struct PlayingAssets {
     // CC0 assets from freesound.org:
     #[asset(path = "audio/sfx/102130__noirpantalon__hard_subby_kick.ogg")]
     pub sfx_enemy_hurt: Handle<AudioSource>,
+    #[asset(path = "audio/sfx/137105__chaosportal__finger-snap-01.ogg")]
+    pub sfx_click: Handle<AudioSource>,
 }
 
 impl Configure for PlayingAssets {
@@ -130,6 +149,7 @@ impl Configure for PlayingAssets {
 #[derive(Actionlike, Reflect, Clone, Hash, PartialEq, Eq, Debug)]
 pub enum PlayingAction {
     TogglePause,
+    ToggleMinimap,
 }
 
 impl Configure for PlayingAction {
@@ -138,9 +158,10 @@ impl Configure for PlayingAction {
         app.insert_resource(
             InputMap::default()
                 .with(Self::TogglePause, GamepadButtonType::Start)
-                .with(Self::TogglePause, KeyCode::Escape)
                 .with(Self::TogglePause, KeyCode::Tab)
-                .with(Self::TogglePause, KeyCode::KeyP),
+                .with(Self::TogglePause, KeyCode::KeyP)
+                .with(Self::ToggleMinimap, GamepadButtonType::Select)
+                .with(Self::ToggleMinimap, KeyCode::KeyM),
         );
         app.add_plugins(InputManagerPlugin::<Self>::default());
         app.add_systems(
@@ -156,6 +177,16 @@ impl Configure for PlayingAction {
                             .and_then(Screen::Playing.will_enter())
                             .and_then(action_just_pressed(Self::TogglePause)),
                     ),
+                // Escape is owned by `FocusAction::Back`, which opens the pause menu during play
+                // and closes it via its back button.
+                PlayingMenu::Pause
+                    .enter()
+                    .in_set(ResolveStateSet::<PlayingMenu>::Compute)
+                    .run_if(
+                        PlayingMenu::is_disabled
+                            .and_then(Screen::Playing.will_enter())
+                            .and_then(action_just_pressed(FocusAction::Back)),
+                    ),
             ),
         );
     }
@@ -166,6 +197,7 @@ impl Configure for PlayingAction {
 #[reflect(Resource)]
 pub enum PlayingMenu {
     Pause,
+    Deck,
     LevelUp,
     Victory,
     Defeat,
@@ -0,0 +1,225 @@
+use bevy::ecs::system::EntityCommand;
+use bevy::ecs::system::SystemState;
+use bevy::prelude::*;
+use bevy_mod_picking::prelude::*;
+use pyri_state::extra::entity_scope::StateScope;
+use pyri_state::prelude::*;
+
+use crate::core::pause::Pause;
+use crate::game::actor::player::IsPlayer;
+use crate::game::audio::music::Beat;
+use crate::game::card::card_with_height;
+use crate::game::card::deck::Deck;
+use crate::game::card::CardConfig;
+use crate::screen::playing::PlayingMenu;
+use crate::ui::prelude::*;
+use crate::util::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        StateFlush,
+        PlayingMenu::Deck.on_edge(Pause::disable, (Pause::enable_default, open_deck_menu)),
+    );
+}
+
+fn open_deck_menu(mut commands: Commands, ui_root: Res<UiRoot>) {
+    commands.spawn_with(deck_overlay).set_parent(ui_root.body);
+    commands.spawn_with(deck_menu).set_parent(ui_root.body);
+}
+
+fn deck_overlay(mut entity: EntityWorldMut) {
+    entity.add(widget::blocking_overlay).insert((
+        Name::new("DeckOverlay"),
+        ZIndex::Global(1),
+        ThemeColor::Overlay.target::<BackgroundColor>(),
+        StateScope::<PlayingMenu>::default(),
+    ));
+}
+
+fn deck_menu(mut entity: EntityWorldMut) {
+    entity
+        .insert((
+            Name::new("DeckMenu"),
+            NodeBundle {
+                style: Style::ABS_COLUMN_MID,
+                z_index: ZIndex::Global(2),
+                ..default()
+            },
+            StateScope::<PlayingMenu>::default(),
+        ))
+        .with_children(|children| {
+            children.spawn_with(header);
+            children.spawn_with(card_grid);
+            children.spawn_with(back_button);
+        });
+}
+
+const HEADER: &str = "deck.header";
+
+fn header(mut entity: EntityWorldMut) {
+    entity.insert((
+        Name::new("Header"),
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font: BOLD_FONT_HANDLE,
+                ..default()
+            },
+        )
+        .with_style(Style {
+            margin: UiRect::vertical(Vw(3.0)),
+            ..default()
+        }),
+        LocalizedText::new(HEADER),
+        DynamicFontSize::new(Vw(4.0)).with_step(8.0),
+        ThemeColorForText(vec![ThemeColor::BodyText]),
+    ));
+}
+
+fn card_grid(entity: Entity, world: &mut World) {
+    let mut system_state = SystemState::<(Res<Beat>, Query<&Deck, With<IsPlayer>>)>::new(world);
+    let (beat, deck_query) = system_state.get(world);
+    let deck = r!(deck_query.get_single());
+    let full_beat = beat.total / 8;
+    let cadence = deck.cadence.max(1);
+    // The number of full beats until the deck plays its next card.
+    let beats_to_next_play = cadence - full_beat % cadence;
+
+    let entries = deck
+        .card_keys
+        .iter()
+        .enumerate()
+        .map(|(i, key)| {
+            let active = i as isize == deck.active;
+            let next_play = if active && deck.busy() > 0 {
+                LocalizedText::new("deck.playing")
+            } else if let Some(plays) = deck.plays_until(i) {
+                let beats = beats_to_next_play + (deck.busy() + plays - 1) * cadence;
+                LocalizedText::new("deck.next").with_args([beats])
+            } else {
+                LocalizedText::new("deck.next_unknown")
+            };
+            (key.clone(), active, next_play)
+        })
+        .collect::<Vec<_>>();
+
+    world
+        .entity_mut(entity)
+        .insert((
+            Name::new("CardGrid"),
+            NodeBundle {
+                style: Style {
+                    width: Vw(90.0),
+                    justify_content: JustifyContent::Center,
+                    flex_wrap: FlexWrap::Wrap,
+                    column_gap: Vw(1.5),
+                    row_gap: Vw(2.0),
+                    ..default()
+                },
+                ..default()
+            },
+        ))
+        .with_children(|children| {
+            for (key, active, next_play) in entries {
+                children.spawn_with(card_entry(key, active, next_play));
+            }
+        });
+}
+
+fn card_entry(
+    key: impl Into<String>,
+    active: bool,
+    next_play: LocalizedText,
+) -> impl EntityCommand {
+    let key = key.into();
+
+    move |entity: Entity, world: &mut World| {
+        let (config, locale) =
+            SystemState::<(ConfigRef<CardConfig>, Locale)>::new(world).get(world);
+        let config = r!(config.get());
+        let card = r!(config.card_map.get(&key));
+        let name = card.localized_name(&key, &locale);
+        let description = card.localized_description(&key, &locale);
+        let category = card.action_key.category_key();
+
+        world
+            .entity_mut(entity)
+            .insert((
+                Name::new("CardEntry"),
+                NodeBundle {
+                    style: Style {
+                        width: Vw(16.5),
+                        align_items: AlignItems::Center,
+                        flex_direction: FlexDirection::Column,
+                        row_gap: Vw(0.5),
+                        ..default()
+                    },
+                    ..default()
+                },
+            ))
+            .with_children(|children| {
+                children.spawn_with(card_with_height(&key, Some(active), Some(Vw(7.0))));
+                children.spawn((
+                    Name::new("CardName"),
+                    TextBundle::from_section(
+                        name,
+                        TextStyle {
+                            font: BOLD_FONT_HANDLE,
+                            ..default()
+                        },
+                    )
+                    .with_text_justify(JustifyText::Center),
+                    DynamicFontSize::new(Vw(1.8)).with_step(8.0),
+                    ThemeColorForText(vec![ThemeColor::BodyText]),
+                ));
+                children.spawn((
+                    Name::new("CardCategory"),
+                    TextBundle::from_section(
+                        "",
+                        TextStyle {
+                            font: FONT_HANDLE,
+                            ..default()
+                        },
+                    ),
+                    LocalizedText::new(category),
+                    DynamicFontSize::new(Vw(1.5)).with_step(8.0),
+                    ThemeColorForText(vec![ThemeColor::Indicator]),
+                ));
+                children
+                    .spawn_with(widget::rich_text(description, ThemeColor::BodyText))
+                    .insert(DynamicFontSize::new(Vw(1.4)).with_step(8.0));
+                children.spawn((
+                    Name::new("CardNextPlay"),
+                    TextBundle::from_section(
+                        "",
+                        TextStyle {
+                            font: FONT_HANDLE,
+                            ..default()
+                        },
+                    ),
+                    next_play,
+                    DynamicFontSize::new(Vw(1.5)).with_step(8.0),
+                    ThemeColorForText(vec![if active {
+                        ThemeColor::Indicator
+                    } else {
+                        ThemeColor::BodyText
+                    }]),
+                ));
+            });
+    }
+}
+
+fn back_button(mut entity: EntityWorldMut) {
+    entity.add(widget::menu_button("deck.back")).insert((
+        On::<Pointer<Click>>::run(PlayingMenu::Pause.enter()),
+        IsBackButton,
+        Style {
+            height: Vw(7.5),
+            width: Vw(30.0),
+            margin: UiRect::vertical(Vw(3.0)),
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+    ));
+}
@@ -1,3 +1,5 @@
+use avian2d::prelude::*;
+use bevy::ecs::system::EntityCommand;
 use bevy::prelude::*;
 use bevy_kira_audio::prelude::*;
 use bevy_mod_picking::prelude::*;
@@ -5,12 +7,17 @@ use pyri_state::extra::entity_scope::StateScope;
 use pyri_state::prelude::*;
 
 use crate::core::pause::Pause;
+use crate::core::UpdateSet;
 use crate::game::actor::health::Health;
 use crate::game::actor::player::IsPlayer;
+use crate::game::audio::mixer::UiChannel;
+use crate::game::combat::death::DeathAnimation;
 use crate::game::combat::death::IsDead;
 use crate::game::combat::death::OnDeath;
+use crate::game::combat::hit::Hurtbox;
 use crate::game::stats::Stats;
 use crate::screen::fade_out;
+use crate::screen::playing::leaderboard::leaderboard_panel;
 use crate::screen::playing::PlayingAssets;
 use crate::screen::playing::PlayingMenu;
 use crate::screen::Screen;
@@ -18,6 +25,7 @@ use crate::ui::prelude::*;
 use crate::util::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
+    app.configure::<DefeatSlowMotion>();
     app.observe(detect_defeat);
 
     app.add_systems(
@@ -26,19 +34,71 @@ pub(super) fn plugin(app: &mut App) {
     );
 }
 
-fn detect_defeat(
-    trigger: Trigger<OnDeath>,
-    player_query: Query<(), With<IsPlayer>>,
+/// The remaining real time to play in slow motion before opening the defeat menu.
+#[derive(Resource, Reflect, Default)]
+#[reflect(Resource)]
+struct DefeatSlowMotion(Option<Timer>);
+
+impl Configure for DefeatSlowMotion {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.init_resource::<Self>();
+        app.add_systems(StateFlush, Screen::ANY.on_exit(reset_defeat_slow_motion));
+        app.add_systems(
+            Update,
+            Screen::Playing.on_update(
+                tick_defeat_slow_motion
+                    .in_set(UpdateSet::TickTimers)
+                    .run_if(Pause::is_disabled),
+            ),
+        );
+    }
+}
+
+fn reset_defeat_slow_motion(
+    mut slow_motion: ResMut<DefeatSlowMotion>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+) {
+    if slow_motion.0.take().is_some() {
+        virtual_time.set_relative_speed(1.0);
+    }
+}
+
+fn tick_defeat_slow_motion(
+    real_time: Res<Time<Real>>,
+    mut slow_motion: ResMut<DefeatSlowMotion>,
+    mut virtual_time: ResMut<Time<Virtual>>,
     mut playing_menu: NextMut<PlayingMenu>,
 ) {
-    let entity = r!(trigger.get_entity());
-    if !player_query.contains(entity) {
+    let timer = rq!(slow_motion.0.as_mut());
+    if !timer.tick(real_time.delta()).finished() {
         return;
     }
 
+    slow_motion.0 = None;
+    virtual_time.set_relative_speed(1.0);
     playing_menu.enter(PlayingMenu::Defeat);
 }
 
+fn detect_defeat(
+    trigger: Trigger<OnDeath>,
+    player_query: Query<Option<&DeathAnimation>, With<IsPlayer>>,
+    mut slow_motion: ResMut<DefeatSlowMotion>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    mut playing_menu: NextMut<PlayingMenu>,
+) {
+    let entity = r!(trigger.get_entity());
+    let death_animation = rq!(player_query.get(entity));
+
+    // Savor the moment in slow motion before opening the defeat menu.
+    if let Some(slow) = death_animation.and_then(|x| x.slow_motion) {
+        virtual_time.set_relative_speed(slow.speed);
+        slow_motion.0 = Some(Timer::from_seconds(slow.secs, TimerMode::Once));
+    } else {
+        playing_menu.enter(PlayingMenu::Defeat);
+    }
+}
+
 fn open_defeat_menu(mut commands: Commands, ui_root: Res<UiRoot>) {
     commands.spawn_with(defeat_overlay).set_parent(ui_root.body);
     commands.spawn_with(defeat_menu).set_parent(ui_root.body);
@@ -54,7 +114,7 @@ fn defeat_overlay(mut entity: EntityWorldMut) {
 }
 
 fn defeat_menu(entity: Entity, world: &mut World) {
-    let stats = *world.resource::<Stats>();
+    let stats = world.resource::<Stats>().clone();
 
     world
         .entity_mut(entity)
@@ -82,19 +142,40 @@ fn defeat_menu(entity: Entity, world: &mut World) {
                 ))
                 .with_children(|children| {
                     children.spawn_with(header);
-                    children.spawn_with(stats);
+                    children.spawn_with(results_row(stats));
                     children.spawn_with(button_container);
                 });
         });
 }
 
-const HEADER: &str = "Party over :(";
+fn results_row(stats: Stats) -> impl EntityCommand<World> {
+    move |mut entity: EntityWorldMut| {
+        entity
+            .insert((
+                Name::new("ResultsRow"),
+                NodeBundle {
+                    style: Style {
+                        align_items: AlignItems::Center,
+                        column_gap: Vw(6.0),
+                        ..default()
+                    },
+                    ..default()
+                },
+            ))
+            .with_children(|children| {
+                children.spawn_with(stats);
+                children.spawn_with(leaderboard_panel);
+            });
+    }
+}
+
+const HEADER: &str = "defeat.header";
 
 fn header(mut entity: EntityWorldMut) {
     entity.insert((
         Name::new("Header"),
         TextBundle::from_section(
-            HEADER,
+            "",
             TextStyle {
                 font: BOLD_FONT_HANDLE,
                 ..default()
@@ -104,6 +185,7 @@ fn header(mut entity: EntityWorldMut) {
             margin: UiRect::top(Vw(4.5)),
             ..default()
         }),
+        LocalizedText::new(HEADER),
         DynamicFontSize::new(Vw(5.0)).with_step(8.0),
         ThemeColorForText(vec![ThemeColor::BodyText]),
     ));
@@ -131,17 +213,23 @@ fn button_container(mut entity: EntityWorldMut) {
 
 fn dance_on_button(mut entity: EntityWorldMut) {
     entity
-        .add(widget::menu_button_with_font_size("Dance on", Vw(3.5)))
+        .add(widget::menu_button_with_font_size(
+            "defeat.dance_on",
+            Vw(3.5),
+        ))
         .insert((
             On::<Pointer<Click>>::run(
                 |mut commands: Commands,
                  mut player_query: Query<(Entity, &mut Health), (With<IsPlayer>, With<IsDead>)>,
-                 audio: Res<Audio>,
+                 audio: Res<AudioChannel<UiChannel>>,
                  assets: Res<PlayingAssets>,
                  mut playing_menu: NextMut<PlayingMenu>| {
                     for (player, mut health) in &mut player_query {
                         health.current = health.max;
-                        commands.entity(player).remove::<IsDead>();
+                        commands
+                            .entity(player)
+                            .remove::<(IsDead, Sensor)>()
+                            .insert(Hurtbox);
                     }
 
                     audio.play(assets.sfx_restart.clone()).with_volume(0.7);
@@ -161,10 +249,15 @@ fn dance_on_button(mut entity: EntityWorldMut) {
 
 fn restart_button(mut entity: EntityWorldMut) {
     entity
-        .add(widget::menu_button_with_font_size("Restart", Vw(3.5)))
+        .add(widget::menu_button_with_font_size(
+            "defeat.restart",
+            Vw(3.5),
+        ))
         .insert((
             On::<Pointer<Click>>::run(
-                |mut commands: Commands, audio: Res<Audio>, assets: Res<PlayingAssets>| {
+                |mut commands: Commands,
+                 audio: Res<AudioChannel<UiChannel>>,
+                 assets: Res<PlayingAssets>| {
                     audio.play(assets.sfx_restart.clone()).with_volume(0.7);
                     commands.spawn_with(fade_out(Screen::Playing));
                 },
@@ -181,7 +274,7 @@ fn restart_button(mut entity: EntityWorldMut) {
 
 fn quit_button(mut entity: EntityWorldMut) {
     entity
-        .add(widget::menu_button_with_font_size("Quit", Vw(3.5)))
+        .add(widget::menu_button_with_font_size("defeat.quit", Vw(3.5)))
         .insert((
             On::<Pointer<Click>>::run(|mut commands: Commands| {
                 commands.spawn_with(fade_out(Screen::Title));
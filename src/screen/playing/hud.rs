@@ -1,15 +1,54 @@
 use bevy::ecs::system::EntityCommand;
 use bevy::ecs::system::SystemState;
 use bevy::prelude::*;
+use serde::Deserialize;
+use serde::Serialize;
 
+use crate::animation::backup::Backup;
+use crate::animation::pulse::OnBeatPulse;
+use crate::animation::pulse::PulseColorFor;
+use crate::animation::pulse::PulseScale;
 use crate::game::actor::level::xp::IsXpBarFill;
 use crate::game::actor::level::IsLevelDisplay;
+use crate::game::audio::timing::IsComboDisplay;
+use crate::game::audio::timing::IsJudgmentPopup;
 use crate::game::card::deck::IsDeckDisplay;
 use crate::game::card::CardConfig;
+use crate::game::score::IsScoreDisplay;
+use crate::screen::playing::minimap::minimap;
 use crate::screen::playing::PlayingAssets;
 use crate::ui::prelude::*;
 use crate::util::prelude::*;
 
+pub(super) fn plugin(app: &mut App) {
+    app.configure::<ConfigHandle<HudConfig>>();
+}
+
+#[derive(Asset, Reflect, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HudConfig {
+    // Off-screen indicators:
+    /// The distance (in pixels) to keep indicators away from the edge of the screen.
+    pub indicator_margin: f32,
+    /// Scale indicators by how far away their target is.
+    pub indicator_scale_by_distance: bool,
+    pub indicator_scale_near: f32,
+    pub indicator_scale_far: f32,
+    /// The distance (in pixels) from the edge of the screen at which indicators reach their far scale.
+    pub indicator_far_distance: f32,
+
+    // Minimap:
+    /// The width and height of the minimap (in UI pixels).
+    pub minimap_size: f32,
+    /// The distance (in pixels) from the camera to the edge of the minimap.
+    pub minimap_range: f32,
+}
+
+impl Config for HudConfig {
+    const PATH: &'static str = "config/hud.ron";
+    const EXTENSION: &'static str = "hud.ron";
+}
+
 pub(super) fn playing_hud(player: Entity) -> impl EntityCommand<World> {
     move |mut entity: EntityWorldMut| {
         entity
@@ -30,6 +69,7 @@ pub(super) fn playing_hud(player: Entity) -> impl EntityCommand<World> {
                 children.spawn_with(upper_hud(player));
                 children.spawn_with(middle_hud);
                 children.spawn_with(lower_hud(player));
+                children.spawn_with(minimap(player));
             });
     }
 }
@@ -55,6 +95,7 @@ fn upper_hud(player: Entity) -> impl EntityCommand<World> {
             .with_children(|children| {
                 children.spawn_with(level_display(player));
                 children.spawn_with(xp_bar(player));
+                children.spawn_with(score_display);
             });
     }
 }
@@ -70,7 +111,7 @@ fn level_display(player: Entity) -> impl EntityCommand<World> {
         entity.insert((
             Name::new("LevelDisplay"),
             TextBundle::from_sections([
-                TextSection::new("Level ", TEXT_STYLE),
+                TextSection::new("", TEXT_STYLE),
                 TextSection::new("", TEXT_STYLE),
                 TextSection::new("/10", TEXT_STYLE),
             ])
@@ -79,13 +120,40 @@ fn level_display(player: Entity) -> impl EntityCommand<World> {
                 margin: UiRect::new(Val::ZERO, Px(-4.0), Px(-4.0), Val::ZERO),
                 ..default()
             }),
+            LocalizedText::new("hud.level"),
             ThemeColorForText(vec![ThemeColor::Indicator; 3]),
+            PulseScale(OnBeatPulse::new(32), 0.1),
+            Backup::<Transform>::default(),
             IsLevelDisplay,
             Selection(player),
         ));
     }
 }
 
+fn score_display(mut entity: EntityWorldMut) {
+    const TEXT_STYLE: TextStyle = TextStyle {
+        font: FONT_HANDLE,
+        font_size: 32.0,
+        color: Color::WHITE,
+    };
+
+    entity.insert((
+        Name::new("ScoreDisplay"),
+        TextBundle::from_sections([
+            TextSection::new("", TEXT_STYLE),
+            TextSection::new("0", TEXT_STYLE),
+        ])
+        .with_no_wrap()
+        .with_style(Style {
+            margin: UiRect::top(Px(-4.0)),
+            ..default()
+        }),
+        LocalizedText::new("hud.score"),
+        ThemeColorForText(vec![ThemeColor::Indicator; 2]),
+        IsScoreDisplay,
+    ));
+}
+
 fn xp_bar(player: Entity) -> impl EntityCommand<World> {
     move |mut entity: EntityWorldMut| {
         let texture = entity
@@ -149,6 +217,7 @@ fn xp_bar_fill(player: Entity) -> impl EntityCommand<World> {
                 ..default()
             },
             ThemeColor::Indicator.target::<BackgroundColor>(),
+            PulseColorFor::<BackgroundColor>::new(OnBeatPulse::new(8), ThemeColor::BodyText, 0.3),
             IsXpBarFill,
             Selection(player),
         ));
@@ -158,7 +227,52 @@ fn xp_bar_fill(player: Entity) -> impl EntityCommand<World> {
 fn middle_hud(mut entity: EntityWorldMut) {
     entity
         .add(Style::ROW_TOP.div())
-        .insert(Name::new("MiddleHud"));
+        .insert(Name::new("MiddleHud"))
+        .with_children(|children| {
+            children.spawn_with(combo_meter);
+        });
+}
+
+fn combo_meter(mut entity: EntityWorldMut) {
+    const TEXT_STYLE: TextStyle = TextStyle {
+        font: FONT_HANDLE,
+        font_size: 32.0,
+        color: Color::WHITE,
+    };
+
+    entity
+        .insert((
+            Name::new("ComboMeter"),
+            NodeBundle {
+                style: Style {
+                    align_items: AlignItems::Center,
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Px(4.0),
+                    ..default()
+                },
+                ..default()
+            },
+        ))
+        .with_children(|children| {
+            children.spawn((
+                Name::new("ComboDisplay"),
+                TextBundle::from_sections([
+                    TextSection::new("", TEXT_STYLE),
+                    TextSection::new("0", TEXT_STYLE),
+                ])
+                .with_no_wrap(),
+                LocalizedText::new("hud.combo"),
+                ThemeColorForText(vec![ThemeColor::Indicator; 2]),
+                PulseScale(OnBeatPulse::new(8), 0.15),
+                Backup::<Transform>::default(),
+                IsComboDisplay,
+            ));
+            children.spawn((
+                Name::new("JudgmentPopup"),
+                NodeBundle::default(),
+                IsJudgmentPopup,
+            ));
+        });
 }
 
 fn lower_hud(player: Entity) -> impl EntityCommand<World> {
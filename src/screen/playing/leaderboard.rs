@@ -0,0 +1,352 @@
+use bevy::ecs::system::EntityCommand;
+use bevy::ecs::system::SystemState;
+use bevy::input::keyboard::Key;
+use bevy::input::keyboard::KeyboardInput;
+use bevy::input::ButtonState;
+use bevy::prelude::*;
+use bevy_mod_picking::prelude::*;
+
+use crate::core::UpdateSet;
+use crate::game::challenge::ActiveChallenge;
+use crate::game::score::LeaderboardEntry;
+use crate::game::score::Leaderboards;
+use crate::game::score::RunMode;
+use crate::game::score::Score;
+use crate::game::score::ScoreConfig;
+use crate::screen::playing::victory_menu::EndlessMode;
+use crate::ui::prelude::*;
+use crate::util::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.configure::<(LeaderboardPanel, NameEntry, OnSubmitName)>();
+}
+
+/// The leaderboard for the current run's mode, with name entry if the score would make the board.
+#[derive(Component, Reflect, Copy, Clone)]
+#[reflect(Component)]
+pub struct LeaderboardPanel {
+    mode: RunMode,
+    score: u64,
+    /// Whether the score can still be submitted.
+    can_submit: bool,
+    /// The index of the submitted entry, to highlight it.
+    submitted: Option<usize>,
+}
+
+impl Configure for LeaderboardPanel {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+    }
+}
+
+/// Show the final score and leaderboard on the victory or defeat menu.
+pub(super) fn leaderboard_panel(entity: Entity, world: &mut World) {
+    let (config, score, leaderboards, challenge, endless_mode) = SystemState::<(
+        ConfigRef<ScoreConfig>,
+        Res<Score>,
+        Res<Leaderboards>,
+        Res<ActiveChallenge>,
+        Res<EndlessMode>,
+    )>::new(world)
+    .get(world);
+    let config = r!(config.get());
+    let mode = RunMode::current(&challenge, &endless_mode);
+    let total = score.total();
+    let can_submit = score.submitted != Some(mode)
+        && leaderboards
+            .rank(mode, total, config.leaderboard_size)
+            .is_some();
+
+    world
+        .entity_mut(entity)
+        .insert((
+            Name::new("LeaderboardPanel"),
+            NodeBundle {
+                style: Style {
+                    align_items: AlignItems::Center,
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Vw(1.0),
+                    ..default()
+                },
+                ..default()
+            },
+            LeaderboardPanel {
+                mode,
+                score: total,
+                can_submit,
+                submitted: None,
+            },
+        ))
+        .add(populate_leaderboard_panel);
+}
+
+fn populate_leaderboard_panel(entity: Entity, world: &mut World) {
+    let panel = *r!(world.get::<LeaderboardPanel>(entity));
+    let leaderboards = world.resource::<Leaderboards>();
+    let entries = leaderboards.board(panel.mode).to_vec();
+    let name = leaderboards.last_name.clone();
+
+    world.entity_mut(entity).with_children(|children| {
+        children.spawn(panel_text(
+            LocalizedText::rich("leaderboard.score").with_args([panel.score]),
+            Vw(3.0),
+            ThemeColor::BodyText,
+        ));
+        children.spawn(panel_text(
+            LocalizedText::new(panel.mode.leaderboard_key()),
+            Vw(2.2),
+            ThemeColor::Indicator,
+        ));
+        if entries.is_empty() {
+            children.spawn(panel_text(
+                LocalizedText::new("leaderboard.empty"),
+                Vw(1.8),
+                ThemeColor::BodyText,
+            ));
+        } else {
+            children.spawn_with(leaderboard_grid(entries, panel.submitted));
+        }
+        if panel.can_submit {
+            children.spawn_with(name_entry_row(name));
+        }
+    });
+}
+
+fn panel_text(text: LocalizedText, font_size: Val, color: ThemeColor) -> impl Bundle {
+    (
+        Name::new("PanelText"),
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font: FONT_HANDLE,
+                ..default()
+            },
+        ),
+        text,
+        DynamicFontSize::new(font_size).with_step(8.0),
+        ThemeColorForText(vec![color]),
+    )
+}
+
+fn leaderboard_grid(
+    entries: Vec<LeaderboardEntry>,
+    highlight: Option<usize>,
+) -> impl EntityCommand<World> {
+    move |mut entity: EntityWorldMut| {
+        entity
+            .insert((
+                Name::new("LeaderboardGrid"),
+                NodeBundle {
+                    style: Style {
+                        display: Display::Grid,
+                        grid_template_columns: RepeatedGridTrack::auto(3),
+                        row_gap: Vw(0.6),
+                        column_gap: Vw(1.5),
+                        ..default()
+                    },
+                    ..default()
+                },
+            ))
+            .with_children(|children| {
+                for (i, entry) in entries.into_iter().enumerate() {
+                    let color = if highlight == Some(i) {
+                        ThemeColor::Indicator
+                    } else {
+                        ThemeColor::BodyText
+                    };
+                    let cells = [
+                        (format!("{}.", i + 1), JustifySelf::End),
+                        (entry.name, JustifySelf::Start),
+                        (entry.score.to_string(), JustifySelf::End),
+                    ];
+                    for (text, justify_self) in cells {
+                        children.spawn((
+                            Name::new("LeaderboardCell"),
+                            TextBundle::from_section(
+                                text,
+                                TextStyle {
+                                    font: FONT_HANDLE,
+                                    ..default()
+                                },
+                            )
+                            .with_style(Style {
+                                justify_self,
+                                ..default()
+                            }),
+                            DynamicFontSize::new(Vw(1.8)).with_step(8.0),
+                            ThemeColorForText(vec![color]),
+                        ));
+                    }
+                }
+            });
+    }
+}
+
+fn name_entry_row(name: String) -> impl EntityCommand<World> {
+    move |mut entity: EntityWorldMut| {
+        entity
+            .insert((
+                Name::new("NameEntryRow"),
+                NodeBundle {
+                    style: Style {
+                        align_items: AlignItems::Center,
+                        column_gap: Vw(2.0),
+                        ..default()
+                    },
+                    ..default()
+                },
+            ))
+            .with_children(|children| {
+                children.spawn_with(name_entry(name));
+                children.spawn_with(save_button);
+            });
+    }
+}
+
+/// The name being typed for a new leaderboard entry.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct NameEntry(pub String);
+
+impl Configure for NameEntry {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(Update, edit_name_entry.in_set(UpdateSet::RecordInput));
+    }
+}
+
+fn name_entry(name: String) -> impl EntityCommand<World> {
+    let style = TextStyle {
+        font: FONT_HANDLE,
+        ..default()
+    };
+
+    move |mut entity: EntityWorldMut| {
+        entity.insert((
+            Name::new("NameEntry"),
+            TextBundle::from_sections([
+                TextSection::new("", style.clone()),
+                TextSection::new(format!("{name}_"), style),
+            ])
+            .with_no_wrap(),
+            LocalizedText::new("leaderboard.name"),
+            DynamicFontSize::new(Vw(2.2)).with_step(8.0),
+            ThemeColorForText(vec![ThemeColor::Indicator, ThemeColor::BodyText]),
+            NameEntry(name),
+            IsTextInput,
+        ));
+    }
+}
+
+fn edit_name_entry(
+    mut commands: Commands,
+    mut keyboard_events: EventReader<KeyboardInput>,
+    config: ConfigRef<ScoreConfig>,
+    mut name_query: Query<(&mut NameEntry, &mut Text)>,
+) {
+    let config = r!(config.get());
+    let (mut entry, mut text) = rq!(name_query.get_single_mut());
+
+    for event in keyboard_events.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+
+        match &event.logical_key {
+            Key::Backspace => {
+                entry.0.pop();
+            },
+            Key::Enter => {
+                commands.trigger(OnSubmitName);
+                continue;
+            },
+            Key::Space => entry.0.push(' '),
+            Key::Character(chars) => entry.0.extend(chars
+                    .chars()
+                    // Only ASCII is supported by the font.
+                    .filter(|c| c.is_ascii_graphic())),
+            _ => continue,
+        }
+        entry.0 = entry.0.chars().take(config.max_name_length).collect();
+    }
+
+    if entry.is_changed() {
+        c!(text.sections.get_mut(1)).value = format!("{}_", entry.0);
+    }
+}
+
+fn save_button(mut entity: EntityWorldMut) {
+    entity
+        .add(widget::menu_button_with_font_size(
+            "leaderboard.save",
+            Vw(2.2),
+        ))
+        .insert((
+            On::<Pointer<Click>>::run(|mut commands: Commands| {
+                commands.trigger(OnSubmitName);
+            }),
+            Style {
+                height: Vw(5.0),
+                width: Vw(12.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+        ));
+}
+
+/// An observable event triggered to add the run's score to the leaderboard.
+#[derive(Event)]
+pub struct OnSubmitName;
+
+impl Configure for OnSubmitName {
+    fn configure(app: &mut App) {
+        app.add_event::<Self>();
+        app.observe(submit_name);
+    }
+}
+
+fn submit_name(
+    _trigger: Trigger<OnSubmitName>,
+    mut commands: Commands,
+    config: ConfigRef<ScoreConfig>,
+    mut score: ResMut<Score>,
+    mut leaderboards: ResMut<Leaderboards>,
+    name_query: Query<&NameEntry>,
+    mut panel_query: Query<(Entity, &mut LeaderboardPanel)>,
+    locale: Locale,
+) {
+    let config = r!(config.get());
+    let (entity, mut panel) = r!(panel_query.get_single_mut());
+    if !panel.can_submit {
+        return;
+    }
+
+    let name = r!(name_query.get_single()).0.trim().to_string();
+    if !name.is_empty() {
+        leaderboards.last_name = name.clone();
+    }
+    let name = if name.is_empty() {
+        locale
+            .get("leaderboard.anonymous")
+            .unwrap_or("?")
+            .to_string()
+    } else {
+        name
+    };
+
+    panel.can_submit = false;
+    panel.submitted = leaderboards.insert(
+        panel.mode,
+        LeaderboardEntry {
+            name,
+            score: panel.score,
+        },
+        config.leaderboard_size,
+    );
+    score.submitted = Some(panel.mode);
+    commands
+        .entity(entity)
+        .despawn_descendants()
+        .add(populate_leaderboard_panel);
+}
@@ -13,10 +13,15 @@ use crate::core::pause::Pause;
 use crate::core::UpdateSet;
 use crate::game::actor::level::up::LevelUp;
 use crate::game::actor::level::Level;
+use crate::game::audio::mixer::UiChannel;
 use crate::game::card::card;
+use crate::game::card::compendium::Compendium;
 use crate::game::card::deck::Deck;
 use crate::game::card::deck::IsDeckDisplay;
 use crate::game::card::CardConfig;
+use crate::game::challenge::RunModifiers;
+use crate::game::challenge::RunRng;
+use crate::game::unlock::Unlocks;
 use crate::screen::playing::PlayingAssets;
 use crate::screen::playing::PlayingMenu;
 use crate::ui::prelude::*;
@@ -88,18 +93,19 @@ fn level_up_menu(mut entity: EntityWorldMut) {
         });
 }
 
-const HEADER: &str = "Level up!";
+const HEADER: &str = "level_up.header";
 
 fn header(mut entity: EntityWorldMut) {
     entity.insert((
         Name::new("Header"),
         TextBundle::from_section(
-            HEADER,
+            "",
             TextStyle {
                 font: BOLD_FONT_HANDLE,
                 ..default()
             },
         ),
+        LocalizedText::new(HEADER),
         DynamicFontSize::new(Vw(4.0)).with_step(8.0),
         ThemeColorForText(vec![ThemeColor::BodyText]),
     ));
@@ -123,7 +129,8 @@ fn instructions_container(mut entity: EntityWorldMut) {
         .with_children(|children| {
             children.spawn((
                 Name::new("FirstLine"),
-                TextBundle::from_sections(parse_rich("You can sort your cards during a level up:")),
+                TextBundle::default(),
+                LocalizedText::rich("level_up.instructions"),
                 DynamicFontSize::new(Vw(3.0)).with_step(8.0),
                 ThemeColorForText(vec![ThemeColor::BodyText]),
             ));
@@ -150,20 +157,20 @@ fn instructions_container(mut entity: EntityWorldMut) {
                     },
                 ))
                 .with_children(|children| {
-                    for (i, text) in [
-                        "[b]Select",
-                        "A/D (or Arrow Keys)",
-                        "[b]Move",
-                        "Shift + A/D (or Arrow Keys)",
-                        "[b]Discard",
-                        "Delete",
+                    for (i, key) in [
+                        "level_up.select",
+                        "level_up.select_keys",
+                        "level_up.move",
+                        "level_up.move_keys",
+                        "level_up.discard",
+                        "level_up.discard_keys",
                     ]
                     .into_iter()
                     .enumerate()
                     {
                         children.spawn((
                             Name::new(format!("Span{}", i)),
-                            TextBundle::from_sections(parse_rich(text)).with_style(Style {
+                            TextBundle::default().with_style(Style {
                                 justify_self: if i % 2 == 0 {
                                     JustifySelf::End
                                 } else {
@@ -171,6 +178,7 @@ fn instructions_container(mut entity: EntityWorldMut) {
                                 },
                                 ..default()
                             }),
+                            LocalizedText::rich(key),
                             DynamicFontSize::new(Vw(3.0)).with_step(8.0),
                             ThemeColorForText(vec![if i % 2 == 0 {
                                 ThemeColor::Indicator
@@ -188,8 +196,12 @@ fn card_options_container(entity: Entity, world: &mut World) {
         ConfigRef<CardConfig>,
         Query<&Selection, With<IsDeckDisplay>>,
         Query<&Level>,
+        Res<Unlocks>,
+        Res<RunModifiers>,
+        ResMut<RunRng>,
     )>::new(world);
-    let (config, selection_query, level_query) = system_state.get(world);
+    let (config, selection_query, level_query, unlocks, modifiers, mut rng) =
+        system_state.get_mut(world);
     let config = r!(config.get());
     let selection = r!(selection_query.get_single());
     let level = r!(level_query.get(selection.0));
@@ -197,12 +209,21 @@ fn card_options_container(entity: Entity, world: &mut World) {
     let card_pool = config
         .card_map
         .iter()
-        .filter(|(_, card)| card.min_level <= level && level <= card.max_level)
+        .filter(|(key, card)| {
+            card.min_level <= level
+                && level <= card.max_level
+                && unlocks.has_card(key, card)
+                && modifiers.allows_offer(card)
+        })
         .collect::<Vec<_>>();
-    let card_keys =
-        r!(card_pool.choose_multiple_weighted(&mut rand::thread_rng(), 3, |(_, card)| card.weight))
-            .map(|(key, _)| (*key).clone())
-            .collect::<Vec<_>>();
+    let card_keys = r!(card_pool.choose_multiple_weighted(&mut rng.0, 3, |(_, card)| card.weight))
+        .map(|(key, _)| (*key).clone())
+        .collect::<Vec<_>>();
+
+    let mut compendium = world.resource_mut::<Compendium>();
+    for key in &card_keys {
+        see(&mut compendium, key);
+    }
 
     world
         .entity_mut(entity)
@@ -256,6 +277,7 @@ fn card_button(key: impl Into<String>) -> impl EntityCommand<World> {
     move |mut entity: EntityWorldMut| {
         entity.add(card(key.clone(), None)).insert((
             Interaction::default(),
+            IsFocused::default(),
             On::<Pointer<Click>>::run(
                 move |deck_display_query: Query<&Selection, With<IsDeckDisplay>>,
                       mut deck_query: Query<&mut Deck>,
@@ -281,11 +303,12 @@ fn card_label(key: impl Into<String>) -> impl EntityCommand {
     let key = key.into();
 
     move |entity: Entity, world: &mut World| {
-        let config = SystemState::<ConfigRef<CardConfig>>::new(world).get(world);
+        let (config, locale) =
+            SystemState::<(ConfigRef<CardConfig>, Locale)>::new(world).get(world);
         let config = r!(config.get());
         let card = r!(config.card_map.get(&key));
         let top = config.card_height * 1.1;
-        let text = card.name.clone();
+        let text = card.localized_name(&key, &locale);
 
         world.entity_mut(entity).insert((
             Name::new("CardLabel"),
@@ -322,7 +345,7 @@ fn button_container(mut entity: EntityWorldMut) {
 }
 
 fn skip_button(mut entity: EntityWorldMut) {
-    entity.add(widget::menu_button("Skip")).insert((
+    entity.add(widget::menu_button("level_up.skip")).insert((
         On::<Pointer<Click>>::run(
             move |mut toggle_query: Query<(&mut Style, &ToggleDisplay)>| {
                 for (mut style, display) in &mut toggle_query {
@@ -345,8 +368,9 @@ fn skip_button(mut entity: EntityWorldMut) {
 }
 
 fn ready_button(mut entity: EntityWorldMut) {
-    entity.add(widget::menu_button("Ready")).insert((
+    entity.add(widget::menu_button("level_up.ready")).insert((
         On::<Pointer<Click>>::run(PlayingMenu::disable),
+        IsBackButton,
         Style {
             display: Display::None,
             height: Vw(8.5),
@@ -371,25 +395,17 @@ pub enum LevelUpMenuAction {
 impl Configure for LevelUpMenuAction {
     fn configure(app: &mut App) {
         app.init_resource::<ActionState<Self>>();
+        // Arrow keys and the D-pad are left free for `FocusAction` to pick a card.
         app.insert_resource(
             InputMap::default()
-                .with(Self::SelectLeft, GamepadButtonType::DPadLeft)
                 .with(Self::SelectLeft, GamepadButtonType::LeftTrigger)
                 .with(Self::SelectLeft, KeyCode::KeyA)
-                .with(Self::SelectLeft, KeyCode::ArrowLeft)
-                .with(Self::SelectRight, GamepadButtonType::DPadRight)
                 .with(Self::SelectRight, GamepadButtonType::RightTrigger)
                 .with(Self::SelectRight, KeyCode::KeyD)
-                .with(Self::SelectRight, KeyCode::ArrowRight)
                 .with(Self::SwapLeft, GamepadButtonType::LeftTrigger2)
                 .with(Self::SwapLeft, ModifierKey::Shift.with(KeyCode::KeyA))
-                .with(Self::SwapLeft, ModifierKey::Shift.with(KeyCode::ArrowLeft))
                 .with(Self::SwapRight, GamepadButtonType::RightTrigger2)
                 .with(Self::SwapRight, ModifierKey::Shift.with(KeyCode::KeyD))
-                .with(
-                    Self::SwapRight,
-                    ModifierKey::Shift.with(KeyCode::ArrowRight),
-                )
                 .with(Self::Discard, GamepadButtonType::West)
                 .with(Self::Discard, KeyCode::Backspace)
                 .with(Self::Discard, KeyCode::Delete),
@@ -441,7 +457,7 @@ fn card_select_right(
 }
 
 fn card_swap_left(
-    audio: Res<Audio>,
+    audio: Res<AudioChannel<UiChannel>>,
     assets: Res<PlayingAssets>,
     deck_display_query: Query<&Selection, With<IsDeckDisplay>>,
     mut deck_query: Query<&mut Deck>,
@@ -456,7 +472,7 @@ fn card_swap_left(
 }
 
 fn card_swap_right(
-    audio: Res<Audio>,
+    audio: Res<AudioChannel<UiChannel>>,
     assets: Res<PlayingAssets>,
     deck_display_query: Query<&Selection, With<IsDeckDisplay>>,
     mut deck_query: Query<&mut Deck>,
@@ -471,7 +487,7 @@ fn card_swap_right(
 }
 
 fn card_discard(
-    audio: Res<Audio>,
+    audio: Res<AudioChannel<UiChannel>>,
     assets: Res<PlayingAssets>,
     deck_display_query: Query<&Selection, With<IsDeckDisplay>>,
     mut deck_query: Query<&mut Deck>,
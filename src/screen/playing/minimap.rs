@@ -0,0 +1,182 @@
+use bevy::ecs::system::EntityCommand;
+use bevy::ecs::system::SystemState;
+use bevy::prelude::*;
+use leafwing_input_manager::common_conditions::action_just_pressed;
+use pyri_state::prelude::*;
+
+use crate::core::camera::CameraRoot;
+use crate::core::settings::Settings;
+use crate::core::UpdateSet;
+use crate::game::actor::enemy::IsEnemy;
+use crate::game::combat::death::IsDead;
+use crate::screen::playing::hud::HudConfig;
+use crate::screen::playing::PlayingAction;
+use crate::screen::Screen;
+use crate::ui::prelude::*;
+use crate::util::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.configure::<(IsMinimap, MinimapDot)>();
+
+    app.add_systems(
+        Update,
+        Screen::Playing.on_update(toggle_minimap.run_if(
+            action_just_pressed(PlayingAction::ToggleMinimap).and_then(not(any_text_input)),
+        )),
+    );
+}
+
+fn toggle_minimap(mut settings: ResMut<Settings>) {
+    settings.minimap = !settings.minimap;
+}
+
+/// A HUD overlay showing nearby actors relative to the camera.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct IsMinimap;
+
+impl Configure for IsMinimap {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(
+            Update,
+            (
+                spawn_enemy_minimap_dots.in_set(UpdateSet::Spawn),
+                update_minimap_visibility.in_set(UpdateSet::SyncLate),
+            ),
+        );
+    }
+}
+
+pub fn minimap(player: Entity) -> impl EntityCommand {
+    move |entity: Entity, world: &mut World| {
+        let config = SystemState::<ConfigRef<HudConfig>>::new(world).get(world);
+        let config = r!(config.get());
+        let size = Px(config.minimap_size);
+
+        world
+            .entity_mut(entity)
+            .insert((
+                Name::new("Minimap"),
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        top: Px(72.0),
+                        right: Px(16.0),
+                        width: size,
+                        height: size,
+                        ..default()
+                    },
+                    border_radius: BorderRadius::all(Px(8.0)),
+                    z_index: ZIndex::Global(2),
+                    ..default()
+                },
+                ThemeColor::Popup.target::<BackgroundColor>(),
+                IsMinimap,
+            ))
+            .with_children(|children| {
+                children.spawn_with(minimap_dot(player, ThemeColor::Indicator));
+            });
+    }
+}
+
+fn update_minimap_visibility(
+    settings: Res<Settings>,
+    mut minimap_query: Query<&mut Style, With<IsMinimap>>,
+) {
+    let display = if settings.minimap {
+        Display::Flex
+    } else {
+        Display::None
+    };
+    for mut style in &mut minimap_query {
+        if style.display != display {
+            style.display = display;
+        }
+    }
+}
+
+fn spawn_enemy_minimap_dots(
+    mut commands: Commands,
+    minimap_query: Query<Entity, With<IsMinimap>>,
+    enemy_query: Query<Entity, Added<IsEnemy>>,
+) {
+    for minimap in &minimap_query {
+        for enemy in &enemy_query {
+            commands
+                .spawn_with(minimap_dot(enemy, ThemeColor::PlayerDamage))
+                .set_parent(minimap);
+        }
+    }
+}
+
+/// A dot on the minimap that follows an actor.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct MinimapDot;
+
+impl Configure for MinimapDot {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(Update, update_minimap_dots.in_set(UpdateSet::SyncLate));
+    }
+}
+
+const DOT_SIZE: f32 = 6.0;
+
+fn minimap_dot(target: Entity, color: ThemeColor) -> impl EntityCommand<World> {
+    move |mut entity: EntityWorldMut| {
+        entity.insert((
+            Name::new("MinimapDot"),
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Px(DOT_SIZE),
+                    height: Px(DOT_SIZE),
+                    margin: UiRect::new(
+                        Px(-DOT_SIZE / 2.0),
+                        Val::ZERO,
+                        Px(-DOT_SIZE / 2.0),
+                        Val::ZERO,
+                    ),
+                    ..default()
+                },
+                border_radius: BorderRadius::MAX,
+                ..default()
+            },
+            color.target::<BackgroundColor>(),
+            Selection(target),
+            MinimapDot,
+        ));
+    }
+}
+
+fn update_minimap_dots(
+    config: ConfigRef<HudConfig>,
+    camera_root: Res<CameraRoot>,
+    camera_query: Query<&GlobalTransform>,
+    target_query: Query<(&GlobalTransform, Has<IsDead>)>,
+    mut despawn: ResMut<LateDespawn>,
+    mut dot_query: Query<(Entity, &Selection, &mut Style), With<MinimapDot>>,
+) {
+    let config = r!(config.get());
+    let center = r!(camera_query.get(camera_root.primary)).translation().xy();
+
+    for (entity, selection, mut style) in &mut dot_query {
+        let Ok((target, is_dead)) = target_query.get(selection.0) else {
+            despawn.recursive(entity);
+            continue;
+        };
+        style.display = if is_dead {
+            Display::None
+        } else {
+            Display::Flex
+        };
+
+        // Map the target's position onto the minimap, pinning far away targets to the edge.
+        let delta = (target.translation().xy() - center) / config.minimap_range;
+        let pos = 50.0 * (Vec2::ONE + delta.clamp(Vec2::NEG_ONE, Vec2::ONE));
+        style.left = Percent(pos.x);
+        style.top = Percent(100.0 - pos.y);
+    }
+}
@@ -1,3 +1,4 @@
+use bevy::ecs::system::EntityCommand;
 use bevy::prelude::*;
 use bevy_kira_audio::prelude::*;
 use bevy_mod_picking::prelude::*;
@@ -5,6 +6,8 @@ use pyri_state::extra::entity_scope::StateScope;
 use pyri_state::prelude::*;
 
 use crate::core::pause::Pause;
+use crate::core::settings::Settings;
+use crate::game::audio::mixer::UiChannel;
 use crate::screen::fade_out;
 use crate::screen::playing::PlayingAssets;
 use crate::screen::playing::PlayingMenu;
@@ -50,13 +53,13 @@ fn pause_menu(mut entity: EntityWorldMut) {
         });
 }
 
-const HEADER: &str = "Paused :|";
+const HEADER: &str = "pause.header";
 
 fn header(mut entity: EntityWorldMut) {
     entity.insert((
         Name::new("Header"),
         TextBundle::from_section(
-            HEADER,
+            "",
             TextStyle {
                 font: BOLD_FONT_HANDLE,
                 ..default()
@@ -66,6 +69,7 @@ fn header(mut entity: EntityWorldMut) {
             margin: UiRect::top(Vw(4.5)),
             ..default()
         }),
+        LocalizedText::new(HEADER),
         DynamicFontSize::new(Vw(5.0)).with_step(8.0),
         ThemeColorForText(vec![ThemeColor::BodyText]),
     ));
@@ -80,7 +84,7 @@ fn button_container(mut entity: EntityWorldMut) {
                     align_items: AlignItems::Center,
                     flex_direction: FlexDirection::Column,
                     margin: UiRect::top(VMin(6.0)),
-                    row_gap: Vw(2.5),
+                    row_gap: Vw(2.0),
                     ..default()
                 },
                 ..default()
@@ -88,16 +92,20 @@ fn button_container(mut entity: EntityWorldMut) {
         ))
         .with_children(|children| {
             children.spawn_with(continue_button);
+            children.spawn_with(deck_button);
+            children.spawn_with(damage_numbers_button);
+            children.spawn_with(volume_grid);
             children.spawn_with(restart_button);
             children.spawn_with(quit_to_title_button);
         });
 }
 
 fn continue_button(mut entity: EntityWorldMut) {
-    entity.add(widget::menu_button("Continue")).insert((
+    entity.add(widget::menu_button("pause.continue")).insert((
         On::<Pointer<Click>>::run(PlayingMenu::disable),
+        IsBackButton,
         Style {
-            height: Vw(9.0),
+            height: Vw(7.5),
             width: Vw(38.0),
             align_items: AlignItems::Center,
             justify_content: JustifyContent::Center,
@@ -106,16 +114,11 @@ fn continue_button(mut entity: EntityWorldMut) {
     ));
 }
 
-fn restart_button(mut entity: EntityWorldMut) {
-    entity.add(widget::menu_button("Restart")).insert((
-        On::<Pointer<Click>>::run(
-            |mut commands: Commands, audio: Res<Audio>, assets: Res<PlayingAssets>| {
-                commands.spawn_with(fade_out(Screen::Playing));
-                audio.play(assets.sfx_restart.clone()).with_volume(0.7);
-            },
-        ),
+fn deck_button(mut entity: EntityWorldMut) {
+    entity.add(widget::menu_button("pause.deck")).insert((
+        On::<Pointer<Click>>::run(PlayingMenu::Deck.enter()),
         Style {
-            height: Vw(9.0),
+            height: Vw(7.5),
             width: Vw(38.0),
             align_items: AlignItems::Center,
             justify_content: JustifyContent::Center,
@@ -124,13 +127,164 @@ fn restart_button(mut entity: EntityWorldMut) {
     ));
 }
 
-fn quit_to_title_button(mut entity: EntityWorldMut) {
-    entity.add(widget::menu_button("Quit to title")).insert((
-        On::<Pointer<Click>>::run(|mut commands: Commands| {
-            commands.spawn_with(fade_out(Screen::Title));
-        }),
+fn damage_numbers_label(enabled: bool) -> &'static str {
+    if enabled {
+        "pause.damage_numbers_on"
+    } else {
+        "pause.damage_numbers_off"
+    }
+}
+
+fn damage_numbers_button(mut entity: EntityWorldMut) {
+    let enabled = entity.world().resource::<Settings>().damage_numbers;
+    entity
+        .add(widget::menu_button_with_font_size(
+            damage_numbers_label(enabled),
+            Vw(3.0),
+        ))
+        .insert((
+            On::<Pointer<Click>>::run(
+                |event: Listener<Pointer<Click>>,
+                 mut settings: ResMut<Settings>,
+                 children_query: Query<&Children>,
+                 mut text_query: Query<&mut LocalizedText>| {
+                    settings.damage_numbers = !settings.damage_numbers;
+                    for &child in r!(children_query.get(event.listener())) {
+                        let mut text = cq!(text_query.get_mut(child));
+                        text.key = damage_numbers_label(settings.damage_numbers).to_string();
+                    }
+                },
+            ),
+            Style {
+                height: Vw(7.5),
+                width: Vw(38.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+        ));
+}
+
+/// A volume in `Settings` that can be adjusted from the pause menu.
+#[derive(Copy, Clone)]
+enum Volume {
+    Music,
+    Sfx,
+    Ui,
+    Voice,
+}
+
+impl Volume {
+    const ALL: [Self; 4] = [Self::Music, Self::Sfx, Self::Ui, Self::Voice];
+
+    /// The fraction of full volume that each click adds, before wrapping back to silent.
+    const STEP: f64 = 0.25;
+
+    fn key(self) -> &'static str {
+        match self {
+            Self::Music => "pause.music_volume",
+            Self::Sfx => "pause.sfx_volume",
+            Self::Ui => "pause.ui_volume",
+            Self::Voice => "pause.voice_volume",
+        }
+    }
+
+    fn get(self, settings: &Settings) -> f64 {
+        match self {
+            Self::Music => settings.music_volume,
+            Self::Sfx => settings.sfx_volume,
+            Self::Ui => settings.ui_volume,
+            Self::Voice => settings.voice_volume,
+        }
+    }
+
+    fn get_mut(self, settings: &mut Settings) -> &mut f64 {
+        match self {
+            Self::Music => &mut settings.music_volume,
+            Self::Sfx => &mut settings.sfx_volume,
+            Self::Ui => &mut settings.ui_volume,
+            Self::Voice => &mut settings.voice_volume,
+        }
+    }
+
+    /// Step to the next volume, wrapping from full volume back to silent.
+    fn cycle(value: f64) -> f64 {
+        let steps = (1.0 / Self::STEP).round();
+        ((value / Self::STEP).round() + 1.0) % (steps + 1.0) * Self::STEP
+    }
+
+    fn percent(value: f64) -> i32 {
+        (value * 100.0).round() as i32
+    }
+}
+
+fn volume_grid(mut entity: EntityWorldMut) {
+    entity
+        .insert((
+            Name::new("VolumeGrid"),
+            NodeBundle {
+                style: Style {
+                    display: Display::Grid,
+                    grid_template_columns: RepeatedGridTrack::flex(2, 1.0),
+                    row_gap: Vw(1.0),
+                    column_gap: Vw(1.0),
+                    width: Vw(38.0),
+                    ..default()
+                },
+                ..default()
+            },
+        ))
+        .with_children(|children| {
+            for volume in Volume::ALL {
+                children.spawn_with(volume_button(volume));
+            }
+        });
+}
+
+fn volume_button(volume: Volume) -> impl EntityCommand<World> {
+    move |mut entity: EntityWorldMut| {
+        let value = volume.get(entity.world().resource::<Settings>());
+        entity
+            .add(widget::menu_button_with_font_size(
+                LocalizedText::new(volume.key()).with_args([Volume::percent(value)]),
+                Vw(2.2),
+            ))
+            .insert((
+                On::<Pointer<Click>>::run(
+                    move |event: Listener<Pointer<Click>>,
+                          mut settings: ResMut<Settings>,
+                          children_query: Query<&Children>,
+                          mut text_query: Query<&mut LocalizedText>| {
+                        let value = volume.get_mut(&mut settings);
+                        *value = Volume::cycle(*value);
+                        for &child in r!(children_query.get(event.listener())) {
+                            let mut text = cq!(text_query.get_mut(child));
+                            text.args = vec![Volume::percent(*value).to_string()];
+                        }
+                    },
+                ),
+                Style {
+                    height: Vw(4.5),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+            ));
+    }
+}
+
+fn restart_button(mut entity: EntityWorldMut) {
+    entity.add(widget::menu_button("pause.restart")).insert((
+        On::<Pointer<Click>>::run(
+            |mut commands: Commands,
+             audio: Res<AudioChannel<UiChannel>>,
+             assets: Res<PlayingAssets>| {
+                commands.spawn_with(fade_out(Screen::Playing));
+                audio.play(assets.sfx_restart.clone()).with_volume(0.7);
+            },
+        ),
         Style {
-            height: Vw(9.0),
+            height: Vw(7.5),
             width: Vw(38.0),
             align_items: AlignItems::Center,
             justify_content: JustifyContent::Center,
@@ -138,3 +292,20 @@ fn quit_to_title_button(mut entity: EntityWorldMut) {
         },
     ));
 }
+
+fn quit_to_title_button(mut entity: EntityWorldMut) {
+    entity
+        .add(widget::menu_button("pause.quit_to_title"))
+        .insert((
+            On::<Pointer<Click>>::run(|mut commands: Commands| {
+                commands.spawn_with(fade_out(Screen::Title));
+            }),
+            Style {
+                height: Vw(7.5),
+                width: Vw(38.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+        ));
+}
@@ -1,3 +1,4 @@
+use bevy::ecs::system::EntityCommand;
 use bevy::prelude::*;
 use bevy_kira_audio::prelude::*;
 use bevy_mod_picking::prelude::*;
@@ -5,8 +6,10 @@ use pyri_state::extra::entity_scope::StateScope;
 use pyri_state::prelude::*;
 
 use crate::core::pause::Pause;
+use crate::game::audio::mixer::UiChannel;
 use crate::game::stats::Stats;
 use crate::screen::fade_out;
+use crate::screen::playing::leaderboard::leaderboard_panel;
 use crate::screen::playing::PlayingAssets;
 use crate::screen::playing::PlayingMenu;
 use crate::screen::Screen;
@@ -55,7 +58,7 @@ fn victory_overlay(mut entity: EntityWorldMut) {
 }
 
 fn victory_menu(entity: Entity, world: &mut World) {
-    let stats = *world.resource::<Stats>();
+    let stats = world.resource::<Stats>().clone();
 
     world
         .entity_mut(entity)
@@ -83,19 +86,40 @@ fn victory_menu(entity: Entity, world: &mut World) {
                 ))
                 .with_children(|children| {
                     children.spawn_with(header);
-                    children.spawn_with(stats);
+                    children.spawn_with(results_row(stats));
                     children.spawn_with(button_container);
                 });
         });
 }
 
-const HEADER: &str = "Life of the party! :)";
+fn results_row(stats: Stats) -> impl EntityCommand<World> {
+    move |mut entity: EntityWorldMut| {
+        entity
+            .insert((
+                Name::new("ResultsRow"),
+                NodeBundle {
+                    style: Style {
+                        align_items: AlignItems::Center,
+                        column_gap: Vw(6.0),
+                        ..default()
+                    },
+                    ..default()
+                },
+            ))
+            .with_children(|children| {
+                children.spawn_with(stats);
+                children.spawn_with(leaderboard_panel);
+            });
+    }
+}
+
+const HEADER: &str = "victory.header";
 
 fn header(mut entity: EntityWorldMut) {
     entity.insert((
         Name::new("Header"),
         TextBundle::from_section(
-            HEADER,
+            "",
             TextStyle {
                 font: BOLD_FONT_HANDLE,
                 ..default()
@@ -105,6 +129,7 @@ fn header(mut entity: EntityWorldMut) {
             margin: UiRect::top(Vw(4.5)),
             ..default()
         }),
+        LocalizedText::new(HEADER),
         DynamicFontSize::new(Vw(5.0)).with_step(8.0),
         ThemeColorForText(vec![ThemeColor::BodyText]),
     ));
@@ -132,7 +157,10 @@ fn button_container(mut entity: EntityWorldMut) {
 
 fn afterparty_button(mut entity: EntityWorldMut) {
     entity
-        .add(widget::menu_button_with_font_size("Afterparty", Vw(3.5)))
+        .add(widget::menu_button_with_font_size(
+            "victory.afterparty",
+            Vw(3.5),
+        ))
         .insert((
             On::<Pointer<Click>>::run(
                 |mut endless_mode: ResMut<EndlessMode>, mut playing_menu: NextMut<PlayingMenu>| {
@@ -152,10 +180,15 @@ fn afterparty_button(mut entity: EntityWorldMut) {
 
 fn restart_button(mut entity: EntityWorldMut) {
     entity
-        .add(widget::menu_button_with_font_size("Restart", Vw(3.5)))
+        .add(widget::menu_button_with_font_size(
+            "victory.restart",
+            Vw(3.5),
+        ))
         .insert((
             On::<Pointer<Click>>::run(
-                |mut commands: Commands, audio: Res<Audio>, assets: Res<PlayingAssets>| {
+                |mut commands: Commands,
+                 audio: Res<AudioChannel<UiChannel>>,
+                 assets: Res<PlayingAssets>| {
                     audio.play(assets.sfx_restart.clone()).with_volume(0.7);
                     commands.spawn_with(fade_out(Screen::Playing));
                 },
@@ -172,7 +205,7 @@ fn restart_button(mut entity: EntityWorldMut) {
 
 fn quit_button(mut entity: EntityWorldMut) {
     entity
-        .add(widget::menu_button_with_font_size("Quit", Vw(3.5)))
+        .add(widget::menu_button_with_font_size("victory.quit", Vw(3.5)))
         .insert((
             On::<Pointer<Click>>::run(|mut commands: Commands| {
                 commands.spawn_with(fade_out(Screen::Title));
@@ -6,6 +6,8 @@ use bevy::render::texture::ImageSampler;
 use iyes_progress::prelude::*;
 use pyri_state::prelude::*;
 
+use crate::core::locale::LocaleConfig;
+use crate::core::theme::ThemeConfig;
 use crate::screen::fade_in;
 use crate::screen::fade_out;
 use crate::screen::Screen;
@@ -23,6 +25,9 @@ pub(super) fn plugin(app: &mut App) {
         Update,
         Screen::Splash.on_update((
             wait(FADE_IN_SECS + SPLASH_SCREEN_MIN_SECS),
+            // The title screen needs these.
+            LocaleConfig::progress.track_progress(),
+            ThemeConfig::progress.track_progress(),
             update_splash.after(TrackedProgressSet),
         )),
     );
@@ -1,7 +1,11 @@
+use bevy::ecs::system::SystemState;
 use bevy::prelude::*;
 use bevy_mod_picking::prelude::*;
 use pyri_state::prelude::*;
 
+use crate::core::locale::LocaleConfig;
+use crate::core::settings::Settings;
+use crate::core::theme::ThemeConfig;
 use crate::screen::fade_in;
 use crate::screen::fade_out;
 use crate::screen::Screen;
@@ -27,13 +31,13 @@ fn title_screen(mut entity: EntityWorldMut) {
         });
 }
 
-const HEADER: &str = "Blobo Party!";
+const HEADER: &str = "title.header";
 
 fn header(mut entity: EntityWorldMut) {
     entity.insert((
         Name::new("Header"),
         TextBundle::from_section(
-            HEADER,
+            "",
             TextStyle {
                 font: BOLD_FONT_HANDLE,
                 ..default()
@@ -43,6 +47,7 @@ fn header(mut entity: EntityWorldMut) {
             margin: UiRect::vertical(Vw(5.0)),
             ..default()
         }),
+        LocalizedText::new(HEADER),
         DynamicFontSize::new(Vw(5.0)).with_step(8.0),
         ThemeColorForText(vec![ThemeColor::BodyText]),
     ));
@@ -66,20 +71,218 @@ fn button_container(mut entity: EntityWorldMut) {
         ))
         .with_children(|children| {
             children.spawn_with(play_button);
+            children.spawn_with(extras_row);
+            children.spawn_with(settings_row);
             children.spawn_with(quit_button);
         });
 }
 
 fn play_button(mut entity: EntityWorldMut) {
     entity
-        .add(widget::menu_button("Play"))
+        .add(widget::menu_button("title.play"))
         .insert(On::<Pointer<Click>>::run(|mut commands: Commands| {
             commands.spawn_with(fade_out(Screen::Intro));
         }));
 }
 
+fn extras_row(mut entity: EntityWorldMut) {
+    entity
+        .insert((
+            Name::new("ExtrasRow"),
+            NodeBundle {
+                style: Style {
+                    column_gap: Vw(2.0),
+                    ..default()
+                },
+                ..default()
+            },
+        ))
+        .with_children(|children| {
+            children.spawn_with(challenge_button);
+            children.spawn_with(calibrate_button);
+            children.spawn_with(compendium_button);
+            children.spawn_with(bestiary_button);
+        });
+}
+
+fn challenge_button(mut entity: EntityWorldMut) {
+    entity
+        .add(widget::menu_button_with_font_size(
+            "title.challenge",
+            Vw(2.5),
+        ))
+        .insert((
+            On::<Pointer<Click>>::run(|mut commands: Commands| {
+                commands.spawn_with(fade_out(Screen::Challenge));
+            }),
+            Style {
+                height: Vw(8.0),
+                width: Vw(20.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+        ));
+}
+
+fn calibrate_button(mut entity: EntityWorldMut) {
+    entity
+        .add(widget::menu_button_with_font_size(
+            "title.calibrate",
+            Vw(2.5),
+        ))
+        .insert((
+            On::<Pointer<Click>>::run(|mut commands: Commands| {
+                commands.spawn_with(fade_out(Screen::Calibration));
+            }),
+            Style {
+                height: Vw(8.0),
+                width: Vw(20.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+        ));
+}
+
+fn compendium_button(mut entity: EntityWorldMut) {
+    entity
+        .add(widget::menu_button_with_font_size(
+            "title.compendium",
+            Vw(2.5),
+        ))
+        .insert((
+            On::<Pointer<Click>>::run(|mut commands: Commands| {
+                commands.spawn_with(fade_out(Screen::Compendium));
+            }),
+            Style {
+                height: Vw(8.0),
+                width: Vw(20.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+        ));
+}
+
+fn bestiary_button(mut entity: EntityWorldMut) {
+    entity
+        .add(widget::menu_button_with_font_size(
+            "title.bestiary",
+            Vw(2.5),
+        ))
+        .insert((
+            On::<Pointer<Click>>::run(|mut commands: Commands| {
+                commands.spawn_with(fade_out(Screen::Bestiary));
+            }),
+            Style {
+                height: Vw(8.0),
+                width: Vw(20.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+        ));
+}
+
+fn settings_row(mut entity: EntityWorldMut) {
+    entity
+        .insert((
+            Name::new("SettingsRow"),
+            NodeBundle {
+                style: Style {
+                    column_gap: Vw(2.0),
+                    ..default()
+                },
+                ..default()
+            },
+        ))
+        .with_children(|children| {
+            children.spawn_with(theme_button);
+            children.spawn_with(locale_button);
+        });
+}
+
+fn theme_button(entity: Entity, world: &mut World) {
+    let config = SystemState::<ConfigRef<ThemeConfig>>::new(world).get(world);
+    let name = config
+        .get()
+        .and_then(ThemeConfig::active)
+        .map(|x| x.name.clone())
+        .unwrap_or_default();
+
+    world
+        .entity_mut(entity)
+        .add(widget::menu_button_with_font_size(
+            LocalizedText::new("title.theme").with_args([name]),
+            Vw(2.5),
+        ))
+        .insert((
+            On::<Pointer<Click>>::run(
+                |event: Listener<Pointer<Click>>,
+                 config: ConfigRef<ThemeConfig>,
+                 mut settings: ResMut<Settings>,
+                 children_query: Query<&Children>,
+                 mut text_query: Query<&mut LocalizedText>| {
+                    let name = r!(config.get().and_then(ThemeConfig::next_name)).to_string();
+                    for &child in r!(children_query.get(event.listener())) {
+                        let mut text = cq!(text_query.get_mut(child));
+                        text.args = vec![name.clone()];
+                    }
+                    settings.theme = name;
+                },
+            ),
+            Style {
+                height: Vw(8.0),
+                width: Vw(30.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+        ));
+}
+
+fn locale_button(entity: Entity, world: &mut World) {
+    let config = SystemState::<ConfigRef<LocaleConfig>>::new(world).get(world);
+    let name = config
+        .get()
+        .and_then(LocaleConfig::active)
+        .map(|x| x.name.clone())
+        .unwrap_or_default();
+
+    world
+        .entity_mut(entity)
+        .add(widget::menu_button_with_font_size(
+            LocalizedText::new("title.locale").with_args([name]),
+            Vw(2.5),
+        ))
+        .insert((
+            On::<Pointer<Click>>::run(
+                |event: Listener<Pointer<Click>>,
+                 config: ConfigRef<LocaleConfig>,
+                 mut settings: ResMut<Settings>,
+                 children_query: Query<&Children>,
+                 mut text_query: Query<&mut LocalizedText>| {
+                    let name = r!(config.get().and_then(LocaleConfig::next_name)).to_string();
+                    for &child in r!(children_query.get(event.listener())) {
+                        let mut text = cq!(text_query.get_mut(child));
+                        text.args = vec![name.clone()];
+                    }
+                    settings.locale = name;
+                },
+            ),
+            Style {
+                height: Vw(8.0),
+                width: Vw(30.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+        ));
+}
+
 fn quit_button(mut entity: EntityWorldMut) {
-    entity.add(widget::menu_button("Quit")).insert((
+    entity.add(widget::menu_button("title.quit")).insert((
         #[cfg(feature = "web")]
         IsDisabled(true),
         #[cfg(not(feature = "web"))]
@@ -3,8 +3,10 @@
 #![allow(dead_code)]
 
 pub mod div;
+pub mod focus;
 pub mod font;
 pub mod interaction;
+pub mod toast;
 pub mod tooltip;
 pub mod widget;
 
@@ -14,17 +16,31 @@ pub mod prelude {
     pub use pyri_tooltip::prelude::*;
 
     pub use super::div::StyleExtDiv as _;
+    pub use super::focus::any_text_input;
+    pub use super::focus::IsBackButton;
+    pub use super::focus::IsFocused;
+    pub use super::focus::IsTextInput;
     pub use super::font::parse_rich;
     pub use super::font::parse_rich_custom;
+    pub use super::font::parse_rich_with_spans;
     pub use super::font::DynamicFontSize;
+    pub use super::font::IconMap;
+    pub use super::font::InlineIcons;
+    pub use super::font::InlineKeywords;
+    pub use super::font::RichSpan;
     pub use super::font::BOLD_FONT_HANDLE;
     pub use super::font::FONT_HANDLE;
     pub use super::font::THICK_FONT_HANDLE;
     pub use super::interaction::InteractionSfx;
     pub use super::interaction::InteractionTable;
     pub use super::interaction::IsDisabled;
+    pub use super::toast::toast;
+    pub use super::tooltip::TooltipColors;
+    pub use super::tooltip::TooltipIcons;
     pub use super::widget;
     pub use super::UiRoot;
+    pub use crate::core::locale::Locale;
+    pub use crate::core::locale::LocalizedText;
     pub use crate::core::theme::ThemeColor;
     pub use crate::core::theme::ThemeColorFor;
     pub use crate::core::theme::ThemeColorForText;
@@ -41,7 +57,13 @@ use crate::util::prelude::*;
 pub(super) fn plugin(app: &mut App) {
     app.configure::<UiRoot>();
 
-    app.add_plugins((font::plugin, interaction::plugin, tooltip::plugin));
+    app.add_plugins((
+        focus::plugin,
+        font::plugin,
+        interaction::plugin,
+        toast::plugin,
+        tooltip::plugin,
+    ));
 }
 
 #[derive(Resource, Reflect)]
@@ -0,0 +1,297 @@
+use std::time::Duration;
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy::render::camera::NormalizedRenderTarget;
+use bevy::window::PrimaryWindow;
+use bevy::window::WindowRef;
+use bevy_kira_audio::prelude::*;
+use bevy_mod_picking::backend::HitData;
+use bevy_mod_picking::pointer::Location;
+use bevy_mod_picking::pointer::PointerButton;
+use bevy_mod_picking::pointer::PointerId;
+use bevy_mod_picking::prelude::*;
+use leafwing_input_manager::common_conditions::action_just_pressed;
+use leafwing_input_manager::prelude::*;
+use rand::Rng as _;
+
+use crate::core::camera::CameraRoot;
+use crate::core::UpdateSet;
+use crate::game::audio::mixer::UiChannel;
+use crate::screen::playing::PlayingAssets;
+use crate::ui::prelude::*;
+use crate::util::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.configure::<(IsFocused, IsBackButton, IsTextInput, FocusAction)>();
+}
+
+/// A component for UI nodes that can be focused with a keyboard or gamepad.
+/// At most one node is focused at a time.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct IsFocused(pub bool);
+
+impl Configure for IsFocused {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(Update, focus_on_hover.in_set(UpdateSet::RecordInput));
+    }
+}
+
+/// A marker component for the button to click on [`FocusAction::Back`].
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct IsBackButton;
+
+impl Configure for IsBackButton {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+    }
+}
+
+/// A marker component for text inputs, which capture typed keys while they exist.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct IsTextInput;
+
+impl Configure for IsTextInput {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+    }
+}
+
+/// A run condition that's true while a text input exists, so keyboard shortcuts can be ignored.
+pub fn any_text_input(text_input_query: Query<(), With<IsTextInput>>) -> bool {
+    !text_input_query.is_empty()
+}
+
+/// A run condition that's true when Enter was typed into a text input.
+fn typed_enter(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    text_input_query: Query<(), With<IsTextInput>>,
+) -> bool {
+    any_text_input(text_input_query)
+        && (keyboard.just_pressed(KeyCode::Enter) || keyboard.just_pressed(KeyCode::NumpadEnter))
+}
+
+#[derive(Actionlike, Eq, PartialEq, Hash, Copy, Clone, Reflect, Debug)]
+pub enum FocusAction {
+    Up,
+    Down,
+    Left,
+    Right,
+    Confirm,
+    Back,
+}
+
+impl Configure for FocusAction {
+    fn configure(app: &mut App) {
+        app.init_resource::<ActionState<Self>>();
+        app.insert_resource(
+            InputMap::default()
+                .with(Self::Up, GamepadButtonType::DPadUp)
+                .with(Self::Up, GamepadControlDirection::LEFT_UP)
+                .with(Self::Up, KeyCode::ArrowUp)
+                .with(Self::Down, GamepadButtonType::DPadDown)
+                .with(Self::Down, GamepadControlDirection::LEFT_DOWN)
+                .with(Self::Down, KeyCode::ArrowDown)
+                .with(Self::Left, GamepadButtonType::DPadLeft)
+                .with(Self::Left, GamepadControlDirection::LEFT_LEFT)
+                .with(Self::Left, KeyCode::ArrowLeft)
+                .with(Self::Right, GamepadButtonType::DPadRight)
+                .with(Self::Right, GamepadControlDirection::LEFT_RIGHT)
+                .with(Self::Right, KeyCode::ArrowRight)
+                .with(Self::Confirm, GamepadButtonType::South)
+                .with(Self::Confirm, KeyCode::Enter)
+                .with(Self::Back, GamepadButtonType::East)
+                .with(Self::Back, KeyCode::Escape),
+        );
+        app.add_plugins(InputManagerPlugin::<Self>::default());
+        app.add_systems(
+            Update,
+            (
+                move_focus,
+                confirm_focus.run_if(action_just_pressed(Self::Confirm).and_then(not(typed_enter))),
+                click_back_button.run_if(action_just_pressed(Self::Back)),
+            )
+                .in_set(UpdateSet::RecordInput),
+        );
+    }
+}
+
+type FocusableData = (
+    &'static Node,
+    &'static GlobalTransform,
+    &'static ViewVisibility,
+    Option<&'static IsDisabled>,
+);
+
+/// Whether a focusable node can currently be interacted with.
+fn is_available(
+    node: &Node,
+    visibility: &ViewVisibility,
+    is_disabled: Option<&IsDisabled>,
+) -> bool {
+    // Nodes under `Display::None` are laid out with zero size.
+    node.size() != Vec2::ZERO && visibility.get() && !matches!(is_disabled, Some(IsDisabled(true)))
+}
+
+/// Set the focused node, unfocusing every other node.
+fn set_focus(target: Option<Entity>, focus_query: &mut Query<(Entity, &mut IsFocused)>) {
+    for (entity, mut is_focused) in focus_query {
+        let focused = Some(entity) == target;
+        if is_focused.0 != focused {
+            is_focused.0 = focused;
+        }
+    }
+}
+
+fn focus_on_hover(
+    hover_query: Query<(Entity, &Interaction), (With<IsFocused>, Changed<Interaction>)>,
+    mut focus_query: Query<(Entity, &mut IsFocused)>,
+) {
+    for (entity, interaction) in &hover_query {
+        if matches!(interaction, Interaction::Hovered) {
+            set_focus(Some(entity), &mut focus_query);
+        }
+    }
+}
+
+/// Collect the available focusable nodes with their focus state and position.
+fn collect_available(
+    focus_query: &Query<(Entity, &mut IsFocused)>,
+    layout_query: &Query<FocusableData>,
+) -> Vec<(Entity, bool, Vec2)> {
+    focus_query
+        .iter()
+        .filter_map(|(entity, is_focused)| {
+            let (node, gt, visibility, is_disabled) = layout_query.get(entity).ok()?;
+            is_available(node, visibility, is_disabled)
+                .then(|| (entity, is_focused.0, gt.translation().xy()))
+        })
+        .collect()
+}
+
+/// Move focus to the nearest available node in the pressed direction.
+fn move_focus(
+    action_state: Res<ActionState<FocusAction>>,
+    assets: Res<PlayingAssets>,
+    audio: Res<AudioChannel<UiChannel>>,
+    mut focus_query: Query<(Entity, &mut IsFocused)>,
+    layout_query: Query<FocusableData>,
+    sfx_query: Query<(), With<InteractionSfx>>,
+) {
+    // In UI coordinates, +Y is down.
+    let direction = [
+        (FocusAction::Up, Vec2::NEG_Y),
+        (FocusAction::Down, Vec2::Y),
+        (FocusAction::Left, Vec2::NEG_X),
+        (FocusAction::Right, Vec2::X),
+    ]
+    .into_iter()
+    .find(|&(action, _)| action_state.just_pressed(&action))
+    .map(|(_, direction)| direction);
+    let direction = rq!(direction);
+    let available = collect_available(&focus_query, &layout_query);
+
+    let target = if let Some(&(current, _, from)) = available.iter().find(|x| x.1) {
+        // Prefer nodes that are well-aligned with the direction over nodes that are closer.
+        let score = |pos: Vec2| {
+            let delta = pos - from;
+            let along = delta.dot(direction);
+            let across = (delta - along * direction).length();
+            (along > 0.0).then_some(along + 2.0 * across)
+        };
+        available
+            .iter()
+            .filter(|x| x.0 != current)
+            .filter_map(|&(entity, _, pos)| score(pos).map(|score| (entity, score)))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(entity, _)| entity)
+    } else {
+        first_available(&available)
+    };
+    let target = rq!(target);
+
+    if sfx_query.contains(target) {
+        audio
+            .play(assets.sfx_ui_hover.clone())
+            .with_volume(0.6)
+            .with_playback_rate(rand::thread_rng().gen_range(0.7..1.6));
+    }
+    set_focus(Some(target), &mut focus_query);
+}
+
+/// The top-left available node, in reading order.
+fn first_available(available: &[(Entity, bool, Vec2)]) -> Option<Entity> {
+    available
+        .iter()
+        .min_by(|a, b| a.2.y.total_cmp(&b.2.y).then(a.2.x.total_cmp(&b.2.x)))
+        .map(|x| x.0)
+}
+
+fn confirm_focus(
+    mut click: ClickFocusable,
+    mut focus_query: Query<(Entity, &mut IsFocused)>,
+    layout_query: Query<FocusableData>,
+) {
+    let available = collect_available(&focus_query, &layout_query);
+    if let Some(&(entity, ..)) = available.iter().find(|x| x.1) {
+        click.click(entity);
+    } else {
+        // Focus something first so the player can see what they're about to click.
+        set_focus(first_available(&available), &mut focus_query);
+    }
+}
+
+fn click_back_button(
+    mut click: ClickFocusable,
+    back_query: Query<(Entity, &Node, &ViewVisibility, Option<&IsDisabled>), With<IsBackButton>>,
+) {
+    for (entity, node, visibility, is_disabled) in &back_query {
+        if is_available(node, visibility, is_disabled) {
+            click.click(entity);
+            return;
+        }
+    }
+}
+
+/// A [`SystemParam`] for clicking UI nodes without a pointer.
+#[derive(SystemParam)]
+struct ClickFocusable<'w, 's> {
+    assets: Res<'w, PlayingAssets>,
+    audio: Res<'w, AudioChannel<UiChannel>>,
+    camera_root: Res<'w, CameraRoot>,
+    window_query: Query<'w, 's, Entity, With<PrimaryWindow>>,
+    sfx_query: Query<'w, 's, (), With<InteractionSfx>>,
+    click_events: EventWriter<'w, Pointer<Click>>,
+}
+
+impl ClickFocusable<'_, '_> {
+    /// Send a synthetic primary click to an entity, as if the mouse had clicked it.
+    fn click(&mut self, entity: Entity) {
+        let window = r!(self.window_query.get_single());
+        let window = r!(WindowRef::Primary.normalize(Some(window)));
+
+        if self.sfx_query.contains(entity) {
+            self.audio
+                .play(self.assets.sfx_ui_click.clone())
+                .with_volume(0.6)
+                .with_playback_rate(rand::thread_rng().gen_range(0.7..1.6));
+        }
+        self.click_events.send(Pointer::new(
+            PointerId::Mouse,
+            Location {
+                target: NormalizedRenderTarget::Window(window),
+                position: Vec2::ZERO,
+            },
+            entity,
+            Click {
+                button: PointerButton::Primary,
+                hit: HitData::new(self.camera_root.primary, 0.0, None, None),
+                duration: Duration::ZERO,
+            },
+        ));
+    }
+}
@@ -1,10 +1,13 @@
 use bevy::asset::load_internal_binary_asset;
 use bevy::prelude::*;
+use bevy::text::TextLayoutInfo;
 use bevy::utils::HashMap;
 use lazy_regex::regex;
 
+use crate::core::theme::ThemeColor;
 use crate::core::window::WindowRoot;
 use crate::core::UpdateSet;
+use crate::ui::prelude::*;
 use crate::util::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
@@ -27,7 +30,13 @@ pub(super) fn plugin(app: &mut App) {
         |bytes: &[u8], _path: String| Font::try_from_bytes(bytes.to_vec()).unwrap()
     );
 
-    app.configure::<DynamicFontSize>();
+    app.configure::<(
+        DynamicFontSize,
+        IconMap,
+        InlineIcons,
+        InlineKeywords,
+        TextOverlay,
+    )>();
 }
 
 pub const FONT_HANDLE: Handle<Font> =
@@ -101,8 +110,19 @@ fn apply_dynamic_font_size(
 }
 
 /// Parses a "rich text" string with tags `"[r]"`, `"[b]"`, and `"[t]"`.
+///
+/// See [`parse_rich_spans`] for the other tags, which are left unresolved here.
 pub fn parse_rich(text: impl AsRef<str>) -> Vec<TextSection> {
-    let styles = HashMap::from([
+    parse_rich_custom(text, &rich_styles(), "r")
+}
+
+/// Parses a "rich text" string with tags `"[r]"`, `"[b]"`, and `"[t]"`, and the tags in [`parse_rich_spans`].
+pub fn parse_rich_with_spans(text: impl AsRef<str>) -> Vec<(TextSection, RichSpan)> {
+    parse_rich_spans(text, &rich_styles(), "r")
+}
+
+fn rich_styles() -> HashMap<&'static str, TextStyle> {
+    HashMap::from([
         (
             "r",
             TextStyle {
@@ -124,66 +144,433 @@ pub fn parse_rich(text: impl AsRef<str>) -> Vec<TextSection> {
                 ..default()
             },
         ),
-    ]);
+    ])
+}
+
+/// Parses a "rich text" string, discarding the [`RichSpan`] of each section.
+pub fn parse_rich_custom(
+    text: impl AsRef<str>,
+    styles: &HashMap<&str, TextStyle>,
+    start_tag: &str,
+) -> Vec<TextSection> {
+    parse_rich_spans(text, styles, start_tag)
+        .into_iter()
+        .map(|(section, _)| section)
+        .collect()
+}
 
-    parse_rich_custom(text, &styles, "r")
+/// The placeholder text for an inline icon. Icons are drawn over it by [`InlineIcons`].
+/// Whitespace isn't laid out as glyphs, so this must be visible text (made invisible by its color).
+pub const ICON_PLACEHOLDER: &str = "##";
+
+/// Extra formatting for a section of rich text, from tags with a value.
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct RichSpan {
+    /// The theme color set by `"[c=Name]"`.
+    pub color: Option<ThemeColor>,
+    /// The keyword inserted by `"[k=key]"`.
+    pub keyword: Option<String>,
+    /// The icon inserted by `"[icon=key]"`.
+    pub icon: Option<String>,
 }
 
-/// Parses a "rich text" string.
+/// Parses a "rich text" string into sections with their [`RichSpan`].
 ///
 /// Format:
 /// - The text style will be set to `styles[start_tag]` initially.
 /// - `"[tag]"` will set the text style to `styles["tag"]` for the following text.
-/// - If `styles["tag"]` is not found, `"[tag]"` will be interpreted as literal text.
-/// - Tags cannot be escaped. To allow literal `"[tag]"`, don't use `"tag"` as a key.
-pub fn parse_rich_custom(
+/// - `"[c=Name]"` will set the theme color to `ThemeColor::Name` for the following text, and `"[c]"` will unset it.
+/// - `"[k=key]"` will insert the keyword `key` as its own section (with underscores as spaces until it's localized).
+/// - `"[icon=key]"` will insert [`ICON_PLACEHOLDER`] as its own section for the icon `key`.
+/// - Invalid tags will be interpreted as literal text.
+/// - `"\["` and `"\\"` will be interpreted as literal `"["` and `"\"`.
+pub fn parse_rich_spans(
     text: impl AsRef<str>,
     styles: &HashMap<&str, TextStyle>,
     start_tag: &str,
-) -> Vec<TextSection> {
+) -> Vec<(TextSection, RichSpan)> {
     let text = text.as_ref();
-    let mut sections = vec![];
+    let mut sections: Vec<(TextSection, RichSpan)> = vec![];
 
     let mut lo = 0;
     let mut style = &styles[start_tag];
-    let mut section = TextSection::new("", style.clone());
+    let mut color = None;
 
-    let mut push_str = |s: &str, style: &TextStyle| {
+    let mut push_str = |s: &str, style: &TextStyle, span: RichSpan| {
         if s.is_empty() {
             return;
         }
 
-        // If the new text uses a different style, create a new section for it.
-        if section.style.font != style.font
-            || section.style.font_size != style.font_size
-            || section.style.color != style.color
-        {
-            let mut old_section = TextSection::new("", style.clone());
-            std::mem::swap(&mut old_section, &mut section);
-            if !old_section.value.is_empty() {
-                sections.push(old_section);
+        // If the new text uses the same style, add it to the current section.
+        // Keywords and icons always get their own section.
+        if let Some((section, last_span)) = sections.last_mut() {
+            if section.style.font == style.font
+                && section.style.font_size == style.font_size
+                && section.style.color == style.color
+                && *last_span == span
+                && span.keyword.is_none()
+                && span.icon.is_none()
+            {
+                section.value.push_str(s);
+                return;
             }
         }
-        section.value.push_str(s);
+        sections.push((TextSection::new(s, style.clone()), span));
     };
 
-    for tag in regex!(r"\[((?:\w|\d|-)+)\]").captures_iter(text) {
-        // Skip invalid tags to include them as literal text instead.
-        let next_style = c!(styles.get(&tag[1]));
-
+    for tag in regex!(r"\\([\\\[])|\[((?:\w|-)+)(?:=((?:\w|-)+))?\]").captures_iter(text) {
         let delim = tag.get(0).unwrap();
-        push_str(&text[lo..delim.start()], style);
+        let color_span = RichSpan { color, ..default() };
+
+        // Include escaped characters as literal text.
+        if let Some(escaped) = tag.get(1) {
+            push_str(&text[lo..delim.start()], style, color_span.clone());
+            push_str(escaped.as_str(), style, color_span);
+            lo = delim.end();
+            continue;
+        }
+
+        // Skip invalid tags to include them as literal text instead.
+        let name = &tag[2];
+        match tag.get(3).map(|x| x.as_str()) {
+            None if styles.contains_key(name) => {
+                push_str(&text[lo..delim.start()], style, color_span);
+                style = &styles[name];
+            },
+            None if name == "c" => {
+                push_str(&text[lo..delim.start()], style, color_span);
+                color = None;
+            },
+            Some(value) if name == "c" => {
+                let next_color = cq!(value.parse::<ThemeColor>().ok());
+                push_str(&text[lo..delim.start()], style, color_span);
+                color = Some(next_color);
+            },
+            Some(key) if name == "k" => {
+                push_str(&text[lo..delim.start()], style, color_span);
+                push_str(
+                    &key.replace('_', " "),
+                    style,
+                    RichSpan {
+                        color,
+                        keyword: Some(key.to_string()),
+                        ..default()
+                    },
+                );
+            },
+            Some(key) if name == "icon" => {
+                push_str(&text[lo..delim.start()], style, color_span);
+                push_str(
+                    ICON_PLACEHOLDER,
+                    style,
+                    RichSpan {
+                        color,
+                        icon: Some(key.to_string()),
+                        ..default()
+                    },
+                );
+            },
+            _ => continue,
+        }
         lo = delim.end();
-        style = next_style;
-    }
-    push_str(&text[lo..text.len()], style);
-    if !section.value.is_empty() {
-        sections.push(section);
     }
+    push_str(
+        &text[lo..text.len()],
+        style,
+        RichSpan { color, ..default() },
+    );
 
     sections
 }
 
+/// Icon textures by key, for `"[icon=key]"` in rich text.
+#[derive(Resource, Default)]
+pub struct IconMap(pub HashMap<String, Handle<Image>>);
+
+impl Configure for IconMap {
+    fn configure(app: &mut App) {
+        app.init_resource::<Self>();
+    }
+}
+
+/// Icons to draw over the [`ICON_PLACEHOLDER`] sections of a text entity, by section index.
+#[derive(Component, Reflect, Default, Clone, PartialEq)]
+#[reflect(Component)]
+pub struct InlineIcons(pub Vec<(usize, String)>);
+
+impl Configure for InlineIcons {
+    fn configure(app: &mut App) {
+        app.register_type::<(Self, InlineIcon)>();
+        app.add_systems(Update, draw_inline_icons.in_set(UpdateSet::SyncLate));
+    }
+}
+
+impl InlineIcons {
+    pub fn from_spans<'a>(spans: impl IntoIterator<Item = &'a RichSpan>) -> Self {
+        Self(
+            spans
+                .into_iter()
+                .enumerate()
+                .filter_map(|(i, span)| Some((i, span.icon.clone()?)))
+                .collect(),
+        )
+    }
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct InlineIcon;
+
+/// Keywords in a text entity that explain themselves in a tooltip on hover, by section index.
+#[derive(Component, Reflect, Default, Clone, PartialEq)]
+#[reflect(Component)]
+pub struct InlineKeywords(pub Vec<(usize, String)>);
+
+impl Configure for InlineKeywords {
+    fn configure(app: &mut App) {
+        app.register_type::<(Self, InlineKeyword)>();
+        app.add_systems(Update, draw_inline_keywords.in_set(UpdateSet::SyncLate));
+    }
+}
+
+impl InlineKeywords {
+    pub fn from_spans<'a>(spans: impl IntoIterator<Item = &'a RichSpan>) -> Self {
+        Self(
+            spans
+                .into_iter()
+                .enumerate()
+                .filter_map(|(i, span)| Some((i, span.keyword.clone()?)))
+                .collect(),
+        )
+    }
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct InlineKeyword;
+
+/// A node drawn over part of a text entity.
+///
+/// Text nodes are measured as leaves, so overlays are spawned as siblings of the text instead of children.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct TextOverlay {
+    text: Entity,
+    /// The area to cover, relative to the text's top-left corner (in logical pixels).
+    rect: Rect,
+}
+
+impl Configure for TextOverlay {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(
+            Update,
+            position_text_overlays
+                .in_set(UpdateSet::SyncLate)
+                .after(draw_inline_icons)
+                .after(draw_inline_keywords),
+        );
+    }
+}
+
+/// The bounding box of the glyphs in a section of laid-out text (in logical pixels).
+fn section_rect(layout: &TextLayoutInfo, section_index: usize, scale_factor: f32) -> Option<Rect> {
+    layout
+        .glyphs
+        .iter()
+        .filter(|glyph| glyph.section_index == section_index)
+        .map(|glyph| Rect::from_center_size(glyph.position, glyph.size))
+        .reduce(|a, b| a.union(b))
+        .map(|rect| Rect {
+            min: rect.min / scale_factor,
+            max: rect.max / scale_factor,
+        })
+}
+
+/// Spawn a [`TextOverlay`] next to its text, hidden until it's positioned.
+fn spawn_text_overlay(
+    commands: &mut Commands,
+    text: Entity,
+    parent: Option<&Parent>,
+    rect: Rect,
+    bundle: impl Bundle,
+) {
+    let mut overlay = commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Px(rect.width()),
+                height: Px(rect.height()),
+                ..default()
+            },
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        TextOverlay { text, rect },
+    ));
+    overlay.insert(bundle);
+    if let Some(parent) = parent {
+        overlay.set_parent(parent.get());
+    }
+}
+
+/// Despawn the overlays of a text entity that match a filter.
+fn despawn_text_overlays<F: bevy::ecs::query::QueryFilter>(
+    commands: &mut Commands,
+    text: Entity,
+    overlay_query: &Query<(Entity, &TextOverlay), F>,
+) {
+    for (entity, overlay) in overlay_query {
+        if overlay.text == text {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+fn draw_inline_icons(
+    mut commands: Commands,
+    window_root: Res<WindowRoot>,
+    window_query: Query<&Window>,
+    icon_map: Res<IconMap>,
+    text_query: Query<
+        (
+            Entity,
+            &InlineIcons,
+            &Text,
+            &TextLayoutInfo,
+            Option<&Parent>,
+        ),
+        Or<(Changed<InlineIcons>, Changed<TextLayoutInfo>)>,
+    >,
+    icon_query: Query<(Entity, &TextOverlay), With<InlineIcon>>,
+) {
+    let window = rq!(window_query.get(window_root.primary));
+    let scale_factor = window.resolution.scale_factor();
+
+    for (entity, icons, text, layout, parent) in &text_query {
+        despawn_text_overlays(&mut commands, entity, &icon_query);
+
+        for (section_index, key) in &icons.0 {
+            let texture = cq!(icon_map.0.get(key)).clone();
+            let size = c!(text.sections.get(*section_index)).style.font_size;
+            // Center the icon over its placeholder glyphs.
+            let center = cq!(section_rect(layout, *section_index, scale_factor)).center();
+
+            spawn_text_overlay(
+                &mut commands,
+                entity,
+                parent,
+                Rect::from_center_size(center, Vec2::splat(size)),
+                (
+                    Name::new(format!("InlineIcon(\"{key}\")")),
+                    UiImage::new(texture),
+                    ThemeColor::BodyText.target::<UiImage>(),
+                    InlineIcon,
+                ),
+            );
+        }
+    }
+}
+
+fn draw_inline_keywords(
+    mut commands: Commands,
+    window_root: Res<WindowRoot>,
+    window_query: Query<&Window>,
+    locale: Locale,
+    text_query: Query<
+        (Entity, &InlineKeywords, &TextLayoutInfo, Option<&Parent>),
+        Or<(Changed<InlineKeywords>, Changed<TextLayoutInfo>)>,
+    >,
+    keyword_query: Query<(Entity, &TextOverlay), With<InlineKeyword>>,
+) {
+    let window = rq!(window_query.get(window_root.primary));
+    let scale_factor = window.resolution.scale_factor();
+
+    for (entity, keywords, layout, parent) in &text_query {
+        despawn_text_overlays(&mut commands, entity, &keyword_query);
+
+        for (section_index, key) in &keywords.0 {
+            let description = cq!(locale.get(&format!("keyword.{key}.description")));
+            let rect = cq!(section_rect(layout, *section_index, scale_factor));
+            let tooltip = locale.parse_rich(description);
+            let tooltip_colors = TooltipColors::from_spans(tooltip.iter().map(|(_, span)| span));
+            let tooltip_text = tooltip
+                .into_iter()
+                .map(|(section, _)| section)
+                .collect::<Vec<_>>();
+
+            spawn_text_overlay(
+                &mut commands,
+                entity,
+                parent,
+                rect,
+                (
+                    Name::new(format!("InlineKeyword(\"{key}\")")),
+                    Interaction::default(),
+                    Tooltip::cursor(tooltip_text),
+                    tooltip_colors,
+                    InlineKeyword,
+                ),
+            );
+        }
+    }
+}
+
+/// Move each [`TextOverlay`] over its text, relative to their shared parent.
+fn position_text_overlays(
+    mut commands: Commands,
+    window_root: Res<WindowRoot>,
+    window_query: Query<&Window>,
+    mut overlay_query: Query<(
+        Entity,
+        &TextOverlay,
+        Option<&Parent>,
+        &mut Style,
+        &mut Visibility,
+    )>,
+    text_query: Query<(&Node, &GlobalTransform)>,
+    parent_query: Query<(&Node, &GlobalTransform, &Style), Without<TextOverlay>>,
+) {
+    let window = rq!(window_query.get(window_root.primary));
+    let viewport_size = window.resolution.size();
+
+    for (entity, overlay, parent, mut style, mut visibility) in &mut overlay_query {
+        let Ok((text_node, text_gt)) = text_query.get(overlay.text) else {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        };
+        let text_top_left = text_gt.translation().xy() - text_node.size() / 2.0;
+
+        // Absolute positions are relative to the inside of the parent's border.
+        let parent_top_left = parent
+            .and_then(|parent| parent_query.get(parent.get()).ok())
+            .map_or(Vec2::ZERO, |(node, gt, style)| {
+                let border = Vec2::new(
+                    style
+                        .border
+                        .left
+                        .resolve(node.size().x, viewport_size)
+                        .unwrap_or_default(),
+                    style
+                        .border
+                        .top
+                        .resolve(node.size().x, viewport_size)
+                        .unwrap_or_default(),
+                );
+                gt.translation().xy() - node.size() / 2.0 + border
+            });
+
+        let position = text_top_left + overlay.rect.min - parent_top_left;
+        if style.left != Px(position.x) || style.top != Px(position.y) {
+            style.left = Px(position.x);
+            style.top = Px(position.y);
+        }
+        if *visibility == Visibility::Hidden {
+            *visibility = Visibility::Inherited;
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -257,11 +644,28 @@ mod tests {
             ),
             (
                 "hello \\[bold] world",
+                vec![TextSection::new("hello [bold] world", r.clone())],
+            ),
+            (
+                "hello \\\\[bold] world",
                 vec![
                     TextSection::new("hello \\", r.clone()),
                     TextSection::new(" world", b.clone()),
                 ],
             ),
+            ("\\[[bold]", vec![TextSection::new("[", r.clone())]),
+            (
+                "hello \\ world",
+                vec![TextSection::new("hello \\ world", r.clone())],
+            ),
+            (
+                "[c=Invalid]",
+                vec![TextSection::new("[c=Invalid]", r.clone())],
+            ),
+            (
+                "[bold=value]",
+                vec![TextSection::new("[bold=value]", r.clone())],
+            ),
             (
                 "hello [regular] world",
                 vec![TextSection::new("hello  world", r.clone())],
@@ -292,4 +696,57 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_spans() {
+        let styles = get_styles();
+        let span = |color, keyword: Option<&str>, icon: Option<&str>| RichSpan {
+            color,
+            keyword: keyword.map(str::to_string),
+            icon: icon.map(str::to_string),
+        };
+        for (case, want) in [
+            ("[c=Indicator]", vec![]),
+            (
+                "hello [c=Indicator]world[c]!",
+                vec![
+                    ("hello ", span(None, None, None)),
+                    ("world", span(Some(ThemeColor::Indicator), None, None)),
+                    ("!", span(None, None, None)),
+                ],
+            ),
+            (
+                "8 [k=contact_damage].",
+                vec![
+                    ("8 ", span(None, None, None)),
+                    ("contact damage", span(None, Some("contact_damage"), None)),
+                    (".", span(None, None, None)),
+                ],
+            ),
+            (
+                "[c=Heal][icon=eighth_rest][icon=eighth_rest]",
+                vec![
+                    (
+                        ICON_PLACEHOLDER,
+                        span(Some(ThemeColor::Heal), None, Some("eighth_rest")),
+                    ),
+                    (
+                        ICON_PLACEHOLDER,
+                        span(Some(ThemeColor::Heal), None, Some("eighth_rest")),
+                    ),
+                ],
+            ),
+            (
+                "\\[k=escaped]",
+                vec![("[k=escaped]", span(None, None, None))],
+            ),
+        ] {
+            let got = parse_rich_spans(case, &styles, "regular");
+            assert_eq!(got.len(), want.len());
+            for ((got, got_span), (want, want_span)) in got.iter().zip(&want) {
+                assert_eq!(got.value, *want);
+                assert_eq!(got_span, want_span);
+            }
+        }
+    }
 }
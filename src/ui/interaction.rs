@@ -6,6 +6,7 @@ use rand::Rng as _;
 
 use crate::animation::offset::Offset;
 use crate::core::UpdateSet;
+use crate::game::audio::mixer::UiChannel;
 use crate::screen::playing::PlayingAssets;
 use crate::ui::prelude::*;
 use crate::util::prelude::*;
@@ -34,6 +35,7 @@ impl Configure for IsDisabled {
 
 // TODO: Text labels are usually child entities, so this is annoying to implement for text colors.
 /// Different values of a component to set for each [`Interaction`] state.
+/// The `focused` value is used instead of `normal` while the entity has [`IsFocused(true)`](IsFocused).
 #[derive(Component, Reflect, Default)]
 #[reflect(Component)]
 pub struct InteractionTable<C: Component> {
@@ -41,6 +43,7 @@ pub struct InteractionTable<C: Component> {
     pub hovered: C,
     pub pressed: C,
     pub disabled: C,
+    pub focused: C,
 }
 
 impl<C: Component + Clone + Reflect + FromReflect + TypePath + GetTypeRegistration> Configure
@@ -59,19 +62,25 @@ fn apply_interaction_table<C: Component + Clone>(
     mut interaction_query: Query<
         (
             Option<&IsDisabled>,
+            Option<&IsFocused>,
             &Interaction,
             &InteractionTable<C>,
             &mut C,
         ),
-        Or<(Changed<Interaction>, Changed<IsDisabled>)>,
+        Or<(
+            Changed<Interaction>,
+            Changed<IsDisabled>,
+            Changed<IsFocused>,
+        )>,
     >,
 ) {
-    for (is_disabled, interaction, table, mut target) in &mut interaction_query {
+    for (is_disabled, is_focused, interaction, table, mut target) in &mut interaction_query {
         // Clone the component from the current `Interaction` state.
         *target = if matches!(is_disabled, Some(IsDisabled(true))) {
             &table.disabled
         } else {
             match interaction {
+                Interaction::None if matches!(is_focused, Some(IsFocused(true))) => &table.focused,
                 Interaction::None => &table.normal,
                 Interaction::Hovered => &table.hovered,
                 Interaction::Pressed => &table.pressed,
@@ -94,7 +103,7 @@ impl Configure for InteractionSfx {
 
 fn play_interaction_sfx(
     assets: Res<PlayingAssets>,
-    audio: Res<Audio>,
+    audio: Res<AudioChannel<UiChannel>>,
     interaction_query: Query<
         (Option<&IsDisabled>, &Interaction),
         (
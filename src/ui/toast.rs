@@ -0,0 +1,124 @@
+use bevy::ecs::system::EntityCommand;
+use bevy::prelude::*;
+use bevy_mod_picking::prelude::*;
+
+use crate::core::UpdateSet;
+use crate::ui::prelude::*;
+use crate::util::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.configure::<(IsToastStack, Toast)>();
+}
+
+/// How long a toast stays on screen (in seconds).
+const TOAST_SECS: f32 = 5.0;
+
+/// The column in the corner of the screen that toasts stack into.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct IsToastStack;
+
+impl Configure for IsToastStack {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+    }
+}
+
+/// A short-lived notification that despawns itself.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Toast(Timer);
+
+impl Configure for Toast {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        // Real time, so toasts still expire while the game is paused.
+        app.add_systems(Update, expire_toasts.in_set(UpdateSet::TickTimers));
+    }
+}
+
+fn expire_toasts(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    mut toast_query: Query<(Entity, &mut Toast)>,
+) {
+    for (entity, mut toast) in &mut toast_query {
+        if toast.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+/// Show a toast in the toast stack, spawning the stack if there isn't one yet.
+pub fn toast(text: impl Into<LocalizedText>) -> impl EntityCommand {
+    let text = text.into();
+
+    move |entity: Entity, world: &mut World| {
+        let stack = match world
+            .query_filtered::<Entity, With<IsToastStack>>()
+            .iter(world)
+            .next()
+        {
+            Some(stack) => stack,
+            None => {
+                let body = world.resource::<UiRoot>().body;
+                world.spawn_with(toast_stack).set_parent(body).id()
+            },
+        };
+
+        world
+            .entity_mut(entity)
+            .insert((
+                Name::new("Toast"),
+                NodeBundle {
+                    style: Style {
+                        padding: UiRect::axes(Vw(1.5), Vw(1.0)),
+                        border: UiRect::all(Vw(0.3)),
+                        ..default()
+                    },
+                    ..default()
+                },
+                ThemeColor::Popup.target::<BackgroundColor>(),
+                ThemeColor::Indicator.target::<BorderColor>(),
+                Toast(Timer::from_seconds(TOAST_SECS, TimerMode::Once)),
+                Pickable::IGNORE,
+            ))
+            .set_parent(stack)
+            .with_children(|children| {
+                children.spawn((
+                    Name::new("ToastText"),
+                    TextBundle::from_section(
+                        "",
+                        TextStyle {
+                            font: FONT_HANDLE,
+                            ..default()
+                        },
+                    ),
+                    text,
+                    DynamicFontSize::new(Vw(1.8)).with_step(8.0),
+                    ThemeColorForText(vec![ThemeColor::BodyText]),
+                ));
+            });
+    }
+}
+
+fn toast_stack(mut entity: EntityWorldMut) {
+    entity.insert((
+        Name::new("ToastStack"),
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                top: Vw(2.0),
+                right: Vw(2.0),
+                align_items: AlignItems::End,
+                flex_direction: FlexDirection::Column,
+                row_gap: Vw(1.0),
+                ..default()
+            },
+            z_index: ZIndex::Global(3),
+            ..default()
+        },
+        IsToastStack,
+        Pickable::IGNORE,
+    ));
+}
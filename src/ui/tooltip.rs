@@ -1,6 +1,8 @@
 use bevy::prelude::*;
 
+use crate::core::UpdateSet;
 use crate::ui::prelude::*;
+use crate::util::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
     let container = app
@@ -27,9 +29,94 @@ pub(super) fn plugin(app: &mut App) {
             TextBundle::default(),
             DynamicFontSize::new(Px(16.0)),
             ThemeColorForText(vec![ThemeColor::BodyText]),
+            InlineIcons::default(),
         ))
         .set_parent(container)
         .id();
 
     app.add_plugins(TooltipPlugin { container, text });
+    app.insert_resource(PrimaryTooltipText(text));
+    app.configure::<(TooltipIcons, TooltipColors)>();
+}
+
+/// The text entity of the primary tooltip.
+#[derive(Resource)]
+struct PrimaryTooltipText(Entity);
+
+/// Icons to draw over the primary tooltip text while this entity's tooltip is shown.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct TooltipIcons(pub InlineIcons);
+
+impl Configure for TooltipIcons {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(Update, sync_tooltip_icons.in_set(UpdateSet::SyncLate));
+    }
+}
+
+/// The theme color of each section of this entity's tooltip text, so the colors follow theme changes.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct TooltipColors(pub Vec<ThemeColor>);
+
+impl Configure for TooltipColors {
+    fn configure(app: &mut App) {
+        app.register_type::<Self>();
+        app.add_systems(Update, sync_tooltip_colors.in_set(UpdateSet::SyncLate));
+    }
+}
+
+impl TooltipColors {
+    /// The colors of rich text spans, falling back to `ThemeColor::BodyText`.
+    pub fn from_spans<'a>(spans: impl IntoIterator<Item = &'a RichSpan>) -> Self {
+        Self(
+            spans
+                .into_iter()
+                .map(|span| span.color.unwrap_or(ThemeColor::BodyText))
+                .collect(),
+        )
+    }
+}
+
+fn sync_tooltip_icons(
+    tooltip_text: Res<PrimaryTooltipText>,
+    hover_query: Query<
+        (&Interaction, Option<&TooltipIcons>),
+        (With<Tooltip>, Changed<Interaction>),
+    >,
+    mut icons_query: Query<&mut InlineIcons>,
+) {
+    for (interaction, icons) in &hover_query {
+        if !matches!(interaction, Interaction::Hovered) {
+            continue;
+        }
+
+        let icons = icons.map(|x| x.0.clone()).unwrap_or_default();
+        let mut tooltip_icons = r!(icons_query.get_mut(tooltip_text.0));
+        if *tooltip_icons != icons {
+            *tooltip_icons = icons;
+        }
+    }
+}
+
+fn sync_tooltip_colors(
+    tooltip_text: Res<PrimaryTooltipText>,
+    hover_query: Query<
+        (&Interaction, Option<&TooltipColors>),
+        (With<Tooltip>, Changed<Interaction>),
+    >,
+    mut colors_query: Query<&mut ThemeColorForText>,
+) {
+    for (interaction, colors) in &hover_query {
+        if !matches!(interaction, Interaction::Hovered) {
+            continue;
+        }
+
+        let colors = colors.map_or_else(|| vec![ThemeColor::BodyText], |x| x.0.clone());
+        let mut tooltip_colors = r!(colors_query.get_mut(tooltip_text.0));
+        if tooltip_colors.0 != colors {
+            tooltip_colors.0 = colors;
+        }
+    }
 }
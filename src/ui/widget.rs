@@ -1,4 +1,5 @@
 use bevy::ecs::system::EntityCommand;
+use bevy::ecs::system::SystemState;
 use bevy::prelude::*;
 use bevy::ui::FocusPolicy;
 use bevy_mod_picking::prelude::*;
@@ -27,19 +28,20 @@ pub fn blocking_overlay(mut entity: EntityWorldMut) {
     });
 }
 
-pub fn menu_button(text: impl Into<String>) -> impl EntityCommand<World> {
-    menu_button_with_font_size(text, Vw(4.0))
+/// A menu button with a localized label.
+pub fn menu_button(label: impl Into<LocalizedText>) -> impl EntityCommand<World> {
+    menu_button_with_font_size(label, Vw(4.0))
 }
 
 pub fn menu_button_with_font_size(
-    text: impl Into<String>,
+    label: impl Into<LocalizedText>,
     font_size: Val,
 ) -> impl EntityCommand<World> {
-    let text = text.into();
+    let label = label.into();
     move |mut entity: EntityWorldMut| {
         entity
             .insert((
-                Name::new(format!("Button(\"{}\")", text)),
+                Name::new(format!("Button(\"{}\")", label.key)),
                 ButtonBundle {
                     style: Style {
                         height: Vw(11.0),
@@ -57,29 +59,50 @@ pub fn menu_button_with_font_size(
                     hovered: ThemeColor::PrimaryHovered.target::<BackgroundColor>(),
                     pressed: ThemeColor::PrimaryPressed.target::<BackgroundColor>(),
                     disabled: ThemeColor::PrimaryDisabled.target::<BackgroundColor>(),
+                    focused: ThemeColor::PrimaryHovered.target::<BackgroundColor>(),
                 },
                 Offset::default(),
                 Backup::<Transform>::default(),
                 InteractionTable {
                     hovered: Offset(Vec2::new(0.0, -4.0)),
                     pressed: Offset(Vec2::new(0.0, 2.0)),
+                    focused: Offset(Vec2::new(0.0, -4.0)),
                     ..default()
                 },
                 InteractionSfx,
+                IsFocused::default(),
             ))
             .with_children(|parent| {
                 parent.spawn((
                     Name::new("ButtonText"),
                     TextBundle::from_section(
-                        text,
+                        "",
                         TextStyle {
                             font: FONT_HANDLE,
                             ..default()
                         },
                     ),
+                    label,
                     DynamicFontSize::new(font_size).with_step(8.0),
                     ThemeColorForText(vec![ThemeColor::PrimaryText]),
                 ));
             });
     }
 }
+
+/// Rich text, localized when it's spawned. Untagged text uses the given color.
+pub fn rich_text(text: impl Into<String>, color: ThemeColor) -> impl EntityCommand {
+    let text = text.into();
+    move |entity: Entity, world: &mut World| {
+        let locale = SystemState::<Locale>::new(world).get(world);
+        let (sections, spans): (Vec<_>, Vec<_>) = locale.parse_rich(&text).into_iter().unzip();
+
+        world.entity_mut(entity).insert((
+            Name::new("RichText"),
+            TextBundle::from_sections(sections),
+            ThemeColorForText(spans.iter().map(|x| x.color.unwrap_or(color)).collect()),
+            InlineIcons::from_spans(&spans),
+            InlineKeywords::from_spans(&spans),
+        ));
+    }
+}
@@ -5,6 +5,8 @@
 pub mod config;
 pub mod late_despawn;
 pub mod patch;
+pub mod persist;
+pub mod seen;
 pub mod selection;
 pub mod texture_atlas_grid;
 pub mod time;
@@ -26,6 +28,10 @@ pub mod prelude {
     pub use super::patch::SpawnWithExt as _;
     pub use super::patch::TriggerExtGetEntity as _;
     pub use super::patch::WorldSpawnWithExt as _;
+    pub use super::persist::Persist;
+    pub use super::persist::Persistent;
+    pub use super::seen::see;
+    pub use super::seen::SeenSet;
     pub use super::selection::Selection;
     pub use super::texture_atlas_grid::TextureAtlasGrid;
 }
@@ -0,0 +1,80 @@
+use std::any::type_name;
+use std::marker::PhantomData;
+
+use bevy::prelude::*;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::util::prelude::*;
+
+/// A resource that's saved between sessions (to disk on native, or to local storage on web).
+pub trait Persist: Resource + Serialize + for<'de> Deserialize<'de> + Default {
+    /// The name to save under.
+    const KEY: &'static str;
+}
+
+/// Loads a [`Persist`] resource on startup, and saves it whenever it changes.
+pub struct Persistent<P: Persist>(PhantomData<P>);
+
+impl<P: Persist> Configure for Persistent<P> {
+    fn configure(app: &mut App) {
+        app.insert_resource(load::<P>());
+        app.add_systems(Last, save::<P>.run_if(resource_changed::<P>));
+    }
+}
+
+fn load<P: Persist>() -> P {
+    let Some(text) = storage::read(P::KEY) else {
+        return P::default();
+    };
+
+    ron::from_str(&text).unwrap_or_else(|e| {
+        warn!("Failed to parse saved {}: {e}", type_name::<P>());
+        P::default()
+    })
+}
+
+fn save<P: Persist>(persist: Res<P>) {
+    let text = r!(ron::ser::to_string_pretty(&*persist, default()));
+    if let Err(e) = storage::write(P::KEY, &text) {
+        warn!("Failed to save {}: {e}", type_name::<P>());
+    }
+}
+
+#[cfg(not(feature = "web"))]
+mod storage {
+    use std::path::PathBuf;
+
+    const SAVE_DIR: &str = "save";
+
+    fn path(key: &str) -> PathBuf {
+        PathBuf::from(SAVE_DIR).join(format!("{key}.ron"))
+    }
+
+    pub fn read(key: &str) -> Option<String> {
+        std::fs::read_to_string(path(key)).ok()
+    }
+
+    pub fn write(key: &str, value: &str) -> std::io::Result<()> {
+        std::fs::create_dir_all(SAVE_DIR)?;
+        std::fs::write(path(key), value)
+    }
+}
+
+#[cfg(feature = "web")]
+mod storage {
+    fn local_storage() -> Option<web_sys::Storage> {
+        web_sys::window()?.local_storage().ok()?
+    }
+
+    pub fn read(key: &str) -> Option<String> {
+        local_storage()?.get_item(key).ok()?
+    }
+
+    pub fn write(key: &str, value: &str) -> Result<(), String> {
+        local_storage()
+            .ok_or("local storage is unavailable")?
+            .set_item(key, value)
+            .map_err(|e| format!("{e:?}"))
+    }
+}
@@ -0,0 +1,30 @@
+use std::ops::DerefMut;
+
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// A set of keys the player has come across, for collections like the compendium.
+#[derive(Reflect, Serialize, Deserialize, Default, Clone)]
+#[serde(transparent)]
+pub struct SeenSet(HashSet<String>);
+
+impl SeenSet {
+    pub fn is_seen(&self, key: &str) -> bool {
+        self.0.contains(key)
+    }
+
+    /// The number of keys that have been seen, out of the given keys.
+    pub fn count_seen<'a>(&self, keys: impl IntoIterator<Item = &'a String>) -> usize {
+        keys.into_iter().filter(|key| self.is_seen(key)).count()
+    }
+}
+
+/// Mark a key as seen, without triggering change detection (and a save) if it was already seen.
+pub fn see<T: DerefMut<Target = SeenSet>>(collection: &mut impl DerefMut<Target = T>, key: &str) {
+    if !collection.is_seen(key) {
+        let seen: &mut SeenSet = collection;
+        seen.0.insert(key.to_string());
+    }
+}